@@ -1,10 +1,33 @@
+use crate::commands::GlobalOptions;
+use crate::config::Settings;
+use crate::error::TaskError;
 use crate::models::{Tag, Task};
+use crate::output;
 use crate::storage::{self, tag_exists};
 use anyhow::Result;
-use chrono::NaiveDate;
+use chrono::{Local, NaiveDate};
+use serde::Serialize;
+
+/// Structured result of `add`
+#[derive(Serialize)]
+struct AddResult<'a> {
+    command: &'static str,
+    dry_run: bool,
+    created_tag: bool,
+    task: &'a Task,
+    /// Workload warnings, e.g. deadlines piling up around the new one
+    warnings: Vec<String>,
+}
+
+/// Parse date from multiple formats: DD.MM.YYYY, YYYY-MM-DD, DD/MM/YYYY.
+/// A configured `input_format` is tried first.
+pub fn parse_date(date_str: &str, input_format: Option<&str>) -> Result<NaiveDate> {
+    if let Some(format) = input_format {
+        if let Ok(date) = NaiveDate::parse_from_str(date_str, format) {
+            return Ok(date);
+        }
+    }
 
-/// Parse date from multiple formats: DD.MM.YYYY, YYYY-MM-DD, DD/MM/YYYY
-pub fn parse_date(date_str: &str) -> Result<NaiveDate> {
     // Try DD.MM.YYYY format
     if let Ok(date) = NaiveDate::parse_from_str(date_str, "%d.%m.%Y") {
         return Ok(date);
@@ -20,29 +43,84 @@ pub fn parse_date(date_str: &str) -> Result<NaiveDate> {
         return Ok(date);
     }
 
-    anyhow::bail!("Invalid date format. Use DD.MM.YYYY, YYYY-MM-DD, or DD/MM/YYYY");
+    Err(
+        TaskError::validation("Invalid date format. Use DD.MM.YYYY, YYYY-MM-DD, or DD/MM/YYYY")
+            .into(),
+    )
+}
+
+/// Warn when enough other active tasks are due within the workload window of `date`,
+/// e.g. "3 other tasks are due within 2 days of 15.02.2026"
+fn workload_warning(
+    tasks: &[Task],
+    task: &Task,
+    date: NaiveDate,
+    settings: &Settings,
+) -> Option<String> {
+    let limits = &settings.workload;
+    let nearby = limits.nearby(tasks, task.id, date).len();
+    if nearby < limits.warn_at as usize {
+        return None;
+    }
+
+    let (noun, verb) = if nearby == 1 {
+        ("task", "is")
+    } else {
+        ("tasks", "are")
+    };
+    let days = if limits.window_days == 1 {
+        "day"
+    } else {
+        "days"
+    };
+    Some(format!(
+        "{} other {} {} due within {} {} of {}",
+        nearby,
+        noun,
+        verb,
+        limits.window_days,
+        days,
+        date.format(&settings.date_output_format)
+    ))
 }
 
-/// Add a new task with optional deadline and tag
-pub fn add_task(name: String, tag: String, deadline: Option<String>) -> Result<()> {
+/// Add a new task with optional deadline and tag. Without a tag, the `default_tag`
+/// setting is used.
+/// With `dry_run` the task that would be created is printed and nothing is saved.
+pub fn add_task(
+    name: String,
+    tag: Option<String>,
+    deadline: Option<String>,
+    settings: &Settings,
+    opts: &GlobalOptions,
+) -> Result<()> {
+    let dry_run = opts.dry_run;
+    let Some(tag) = tag.or_else(|| settings.default_tag.clone()) else {
+        return Err(TaskError::validation("No tag given and no default_tag is configured").into());
+    };
+
     // Validate inputs
     if name.is_empty() {
-        anyhow::bail!("Task name cannot be empty");
+        return Err(TaskError::validation("Task name cannot be empty").into());
     }
     if name.len() > 200 {
-        anyhow::bail!("Task name too long (max 200 characters)");
+        return Err(TaskError::validation("Task name too long (max 200 characters)").into());
     }
     if tag.is_empty() {
-        anyhow::bail!("Tag name cannot be empty");
+        return Err(TaskError::validation("Tag name cannot be empty").into());
     }
 
     // Parse deadline if provided
     let parsed_deadline = match deadline {
-        Some(date_str) => Some(parse_date(&date_str)?),
+        Some(date_str) => Some(parse_date(
+            &date_str,
+            settings.date_input_format.as_deref(),
+        )?),
         None => None,
     };
 
     // Load existing data
+    let _lock = storage::lock_storage()?;
     let mut tasks = storage::load_tasks()?;
     let mut tags = storage::load_tags()?;
 
@@ -58,29 +136,54 @@ pub fn add_task(name: String, tag: String, deadline: Option<String>) -> Result<(
     }
 
     // Create task
-    let task = Task::new(id, name.clone(), tag.clone(), parsed_deadline);
+    let mut task = Task::new(id, name.clone(), tag.clone(), parsed_deadline);
+    task.created_at = Some(Local::now());
     task.validate()?;
-    tasks.push(task);
+    tasks.push(task.clone());
+
+    let warnings: Vec<String> = parsed_deadline
+        .and_then(|date| workload_warning(&tasks, &task, date, settings))
+        .into_iter()
+        .collect();
 
     // Save to storage
-    storage::save_tasks(&tasks)?;
-    storage::save_tags(&tags)?;
+    if !dry_run {
+        storage::save_tasks(&tasks)?;
+        storage::save_tags(&tags)?;
+    }
+
+    if opts.output.is_structured() {
+        let result = AddResult {
+            command: "add",
+            dry_run,
+            created_tag: new_tag,
+            task: &task,
+            warnings,
+        };
+        output::print_result(opts.output, &result);
+        return Ok(());
+    }
+
+    let verb = if dry_run { "Would create" } else { "Created" };
 
     // Output messages
     if new_tag {
-        println!("Created new tag: {}", tag);
+        println!("{} new tag: {}", verb, tag);
     }
 
     // Format deadline for output
     let deadline_str = match parsed_deadline {
-        Some(date) => format!("due {}", date.format("%d.%m.%Y")),
+        Some(date) => format!("due {}", date.format(&settings.date_output_format)),
         None => "no deadline".to_string(),
     };
 
     println!(
-        "Created task {:03}: {} ({}, {})",
-        id, name, tag, deadline_str
+        "{} task {:03}: {} ({}, {})",
+        verb, id, name, tag, deadline_str
     );
+    for warning in &warnings {
+        eprintln!("Warning: {}", warning);
+    }
 
     Ok(())
 }
@@ -92,7 +195,7 @@ mod tests {
 
     #[test]
     fn test_parse_date_dd_mm_yyyy() {
-        let date = parse_date("15.02.2026").unwrap();
+        let date = parse_date("15.02.2026", None).unwrap();
         assert_eq!(date.year(), 2026);
         assert_eq!(date.month(), 2);
         assert_eq!(date.day(), 15);
@@ -100,7 +203,7 @@ mod tests {
 
     #[test]
     fn test_parse_date_yyyy_mm_dd() {
-        let date = parse_date("2026-02-15").unwrap();
+        let date = parse_date("2026-02-15", None).unwrap();
         assert_eq!(date.year(), 2026);
         assert_eq!(date.month(), 2);
         assert_eq!(date.day(), 15);
@@ -108,7 +211,7 @@ mod tests {
 
     #[test]
     fn test_parse_date_dd_slash_mm_yyyy() {
-        let date = parse_date("15/02/2026").unwrap();
+        let date = parse_date("15/02/2026", None).unwrap();
         assert_eq!(date.year(), 2026);
         assert_eq!(date.month(), 2);
         assert_eq!(date.day(), 15);
@@ -116,6 +219,34 @@ mod tests {
 
     #[test]
     fn test_parse_date_invalid() {
-        assert!(parse_date("2026-99-99").is_err());
+        assert!(parse_date("2026-99-99", None).is_err());
+    }
+
+    #[test]
+    fn test_parse_date_configured_format_first() {
+        // Month-first input wins over the built-in DD/MM/YYYY
+        let date = parse_date("02/15/2026", Some("%m/%d/%Y")).unwrap();
+        assert_eq!((date.month(), date.day()), (2, 15));
+        let date = parse_date("15.02.2026", Some("%m/%d/%Y")).unwrap();
+        assert_eq!((date.month(), date.day()), (2, 15));
+    }
+
+    #[test]
+    fn test_workload_warning() {
+        let day = |d: u32| NaiveDate::from_ymd_opt(2026, 2, d).unwrap();
+        let tasks: Vec<Task> = [13, 14, 16, 20]
+            .iter()
+            .enumerate()
+            .map(|(i, &d)| Task::new(i as u32 + 1, "T".to_string(), "X".to_string(), Some(day(d))))
+            .collect();
+        let new = Task::new(9, "New".to_string(), "X".to_string(), Some(day(15)));
+        let settings = Settings::default();
+
+        assert_eq!(
+            workload_warning(&tasks, &new, day(15), &settings).as_deref(),
+            Some("3 other tasks are due within 2 days of 15.02.2026")
+        );
+        // A single nearby task stays below the default threshold of two
+        assert_eq!(workload_warning(&tasks, &new, day(21), &settings), None);
     }
 }
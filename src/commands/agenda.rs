@@ -0,0 +1,237 @@
+use crate::commands::GlobalOptions;
+use crate::config::Settings;
+use crate::models::Task;
+use crate::output;
+use crate::storage;
+use crate::ui;
+use anyhow::Result;
+use chrono::{Datelike, Duration, Local, NaiveDate};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Time bucket of the agenda, in display order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum Bucket {
+    Overdue,
+    Today,
+    Tomorrow,
+    ThisWeek,
+    NextWeek,
+    Later,
+    NoDeadline,
+}
+
+impl Bucket {
+    /// Header shown above the bucket's tasks
+    fn title(self) -> &'static str {
+        match self {
+            Bucket::Overdue => "Overdue",
+            Bucket::Today => "Today",
+            Bucket::Tomorrow => "Tomorrow",
+            Bucket::ThisWeek => "This week",
+            Bucket::NextWeek => "Next week",
+            Bucket::Later => "Later",
+            Bucket::NoDeadline => "No deadline",
+        }
+    }
+}
+
+/// A task in the structured result of `agenda`
+#[derive(Serialize)]
+struct AgendaEntry<'a> {
+    bucket: Bucket,
+    #[serde(flatten)]
+    task: &'a Task,
+}
+
+/// Place a deadline into a bucket. Weeks start on Monday; "this week" is the rest of the
+/// current week after tomorrow.
+fn bucket_for(deadline: Option<NaiveDate>, today: NaiveDate) -> Bucket {
+    let Some(deadline) = deadline else {
+        return Bucket::NoDeadline;
+    };
+
+    let end_of_week = today + Duration::days(6 - today.weekday().num_days_from_monday() as i64);
+    match (deadline - today).num_days() {
+        d if d < 0 => Bucket::Overdue,
+        0 => Bucket::Today,
+        1 => Bucket::Tomorrow,
+        _ if deadline <= end_of_week => Bucket::ThisWeek,
+        _ if deadline <= end_of_week + Duration::days(7) => Bucket::NextWeek,
+        _ => Bucket::Later,
+    }
+}
+
+/// Group active tasks into time buckets, soonest deadline first within each bucket.
+/// With a horizon of `days`, tasks due later than `days` days from today are left out.
+fn bucket_tasks(
+    tasks: Vec<Task>,
+    days: Option<u32>,
+    today: NaiveDate,
+) -> BTreeMap<Bucket, Vec<Task>> {
+    let horizon = days.map(|d| today + Duration::days(d as i64));
+    let mut buckets: BTreeMap<Bucket, Vec<Task>> = BTreeMap::new();
+
+    for task in tasks.into_iter().filter(|t| !t.completed) {
+        if let (Some(deadline), Some(horizon)) = (task.deadline, horizon) {
+            if deadline > horizon {
+                continue;
+            }
+        }
+        buckets
+            .entry(bucket_for(task.deadline, today))
+            .or_default()
+            .push(task);
+    }
+
+    for tasks in buckets.values_mut() {
+        tasks.sort_by_key(|t| (t.deadline, t.id));
+    }
+    buckets
+}
+
+/// Show active tasks grouped by when they are due: Overdue, Today, Tomorrow, This week,
+/// Next week, Later and No deadline. Each row shows the tag inline:
+/// `{id:03} - {name} [{tag}]{padding}{date}`. `days` limits how far ahead to look.
+pub fn show_agenda(days: Option<u32>, settings: &Settings, opts: &GlobalOptions) -> Result<()> {
+    let tasks = storage::load_tasks()?;
+    let today = Local::now().date_naive();
+    let buckets = bucket_tasks(tasks, days, today);
+
+    if opts.output.is_structured() {
+        let entries: Vec<AgendaEntry> = buckets
+            .iter()
+            .flat_map(|(&bucket, tasks)| tasks.iter().map(move |task| AgendaEntry { bucket, task }))
+            .collect();
+        output::print_records(opts.output, "agenda", "tasks", &entries);
+        return Ok(());
+    }
+
+    if buckets.is_empty() {
+        println!("No upcoming tasks");
+        return Ok(());
+    }
+
+    let theme = &settings.theme;
+    let color = opts.color.enabled();
+    let indent = " ".repeat(theme.indent);
+    let label = |t: &Task| format!("{:03} - {} [{}]", t.id, t.name, t.tag);
+
+    // Fit labels to the terminal, leaving room for the indent, padding and date
+    let date_width = buckets
+        .values()
+        .flatten()
+        .filter_map(|t| t.deadline)
+        .map(|d| ui::display_width(&d.format(&theme.date_format).to_string()))
+        .max()
+        .unwrap_or(0);
+    let mut label_width = buckets
+        .values()
+        .flatten()
+        .map(|t| ui::display_width(&label(t)))
+        .max()
+        .unwrap_or(0);
+    if let Some(w) = ui::terminal_width() {
+        label_width = label_width.min(w.saturating_sub(theme.indent + theme.padding + date_width));
+    }
+
+    for (bucket, tasks) in &buckets {
+        if color {
+            println!("{}", theme.header.paint(bucket.title()));
+        } else {
+            println!("{}", bucket.title());
+        }
+
+        for task in tasks {
+            let label = ui::truncate_to_width(&label(task), label_width);
+            let mut line = label.clone();
+            if let Some(deadline) = task.deadline {
+                line.push_str(
+                    &" ".repeat(
+                        label_width.saturating_sub(ui::display_width(&label)) + theme.padding,
+                    ),
+                );
+                line.push_str(&deadline.format(&theme.date_format).to_string());
+            }
+
+            let style = match bucket {
+                Bucket::Overdue => Some(&theme.overdue),
+                Bucket::Today => Some(&theme.due_today),
+                _ => None,
+            };
+            match style {
+                Some(style) if color => println!("{}{}", indent, style.paint(&line)),
+                _ => println!("{}{}", indent, line),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::testing::date;
+
+    fn mk_task(id: u32, day: Option<u32>) -> Task {
+        Task::new(id, format!("T{}", id), "Tag".to_string(), day.map(date))
+    }
+
+    #[test]
+    fn test_bucket_for() {
+        let today = date(17);
+        assert_eq!(bucket_for(Some(date(16)), today), Bucket::Overdue);
+        assert_eq!(bucket_for(Some(date(17)), today), Bucket::Today);
+        assert_eq!(bucket_for(Some(date(18)), today), Bucket::Tomorrow);
+        assert_eq!(bucket_for(Some(date(22)), today), Bucket::ThisWeek);
+        assert_eq!(bucket_for(Some(date(23)), today), Bucket::NextWeek);
+        assert_eq!(
+            bucket_for(Some(date(1).with_month(3).unwrap()), today),
+            Bucket::NextWeek
+        );
+        assert_eq!(
+            bucket_for(Some(date(2).with_month(3).unwrap()), today),
+            Bucket::Later
+        );
+        assert_eq!(bucket_for(None, today), Bucket::NoDeadline);
+    }
+
+    #[test]
+    fn test_bucket_for_on_sunday() {
+        // Tomorrow is already next week, so "this week" is empty
+        let sunday = date(22);
+        assert_eq!(bucket_for(Some(date(23)), sunday), Bucket::Tomorrow);
+        assert_eq!(bucket_for(Some(date(24)), sunday), Bucket::NextWeek);
+    }
+
+    #[test]
+    fn test_bucket_tasks_horizon_and_completed() {
+        let mut done = mk_task(1, Some(17));
+        done.completed = true;
+        let tasks = vec![
+            done,
+            mk_task(2, Some(25)),
+            mk_task(3, Some(19)),
+            mk_task(4, Some(18)),
+            mk_task(5, None),
+        ];
+
+        let buckets = bucket_tasks(tasks.clone(), None, date(17));
+        assert_eq!(
+            buckets.keys().copied().collect::<Vec<_>>(),
+            vec![
+                Bucket::Tomorrow,
+                Bucket::ThisWeek,
+                Bucket::NextWeek,
+                Bucket::NoDeadline
+            ]
+        );
+
+        // A two-day horizon drops tasks due later but keeps tasks without deadline
+        let buckets = bucket_tasks(tasks, Some(2), date(17));
+        let ids: Vec<u32> = buckets.values().flatten().map(|t| t.id).collect();
+        assert_eq!(ids, vec![4, 3, 5]);
+    }
+}
@@ -0,0 +1,223 @@
+use crate::commands::remove::parse_id;
+use crate::commands::GlobalOptions;
+use crate::error::TaskError;
+use crate::models::{ArchivedTask, Tag, Task};
+use crate::output;
+use crate::storage::{self, tag_exists};
+use anyhow::Result;
+use serde::Serialize;
+
+/// A single restored entry in the structured result of `archive restore`
+#[derive(Serialize)]
+struct RestoredEntry {
+    archive_id: u32,
+    task: Task,
+}
+
+/// Structured result of `archive restore`
+#[derive(Serialize)]
+struct RestoreResult {
+    command: &'static str,
+    dry_run: bool,
+    restored: Vec<RestoredEntry>,
+    not_found: Vec<String>,
+}
+
+/// Check whether an archived task matches a search query.
+/// Matching is a case-insensitive substring search over the task name and tag.
+fn matches_query(entry: &ArchivedTask, query: &str) -> bool {
+    let query = query.to_lowercase();
+    entry.task.name.to_lowercase().contains(&query)
+        || entry.task.tag.to_lowercase().contains(&query)
+}
+
+/// Format a single archive entry as one output line, with dates in `date_format`
+fn format_entry(entry: &ArchivedTask, date_format: &str) -> String {
+    let deadline_str = match entry.task.deadline {
+        Some(date) => format!("due {}", date.format(date_format)),
+        None => "no deadline".to_string(),
+    };
+    format!(
+        "{:03} - {} ({}, {}, archived {})",
+        entry.id,
+        entry.task.name,
+        entry.task.tag,
+        deadline_str,
+        entry.archived_on.format(date_format)
+    )
+}
+
+/// Print archive entries sorted by archive ID
+fn print_entries(
+    mut entries: Vec<&ArchivedTask>,
+    command: &str,
+    date_format: &str,
+    opts: &GlobalOptions,
+) {
+    entries.sort_by_key(|e| e.id);
+
+    if opts.output.is_structured() {
+        output::print_records(opts.output, command, "entries", &entries);
+        return;
+    }
+
+    for entry in entries {
+        println!("{}", format_entry(entry, date_format));
+    }
+}
+
+/// List every task stored in the archive
+pub fn list_archive(date_format: &str, opts: &GlobalOptions) -> Result<()> {
+    let archive = storage::load_archive()?;
+
+    if archive.is_empty() && !opts.output.is_structured() {
+        println!("Archive is empty");
+        return Ok(());
+    }
+
+    print_entries(archive.iter().collect(), "archive list", date_format, opts);
+
+    Ok(())
+}
+
+/// List archived tasks whose name or tag contains `query`
+pub fn search_archive(query: String, date_format: &str, opts: &GlobalOptions) -> Result<()> {
+    if query.is_empty() {
+        return Err(TaskError::validation("Search query cannot be empty").into());
+    }
+
+    let archive = storage::load_archive()?;
+    let matches: Vec<&ArchivedTask> = archive
+        .iter()
+        .filter(|e| matches_query(e, &query))
+        .collect();
+
+    if matches.is_empty() && !opts.output.is_structured() {
+        println!("No archived tasks match \"{}\"", query);
+        return Ok(());
+    }
+
+    print_entries(matches, "archive search", date_format, opts);
+
+    Ok(())
+}
+
+/// Move archived tasks back into the active task list.
+/// Restored tasks receive a fresh task ID, since their original ID may have been recycled.
+/// They keep their completed state unless `reopen` is set.
+/// With `dry_run` the tasks that would be restored are printed and nothing is saved.
+pub fn restore_tasks(ids: Vec<String>, reopen: bool, opts: &GlobalOptions) -> Result<()> {
+    let dry_run = opts.dry_run;
+
+    if ids.is_empty() {
+        return Err(TaskError::validation("No IDs provided").into());
+    }
+
+    let _lock = storage::lock_storage()?;
+    let mut archive = storage::load_archive()?;
+    let mut tasks = storage::load_tasks()?;
+    let mut tags = storage::load_tags()?;
+
+    let mut not_found: Vec<String> = Vec::new();
+    let mut restored: Vec<RestoredEntry> = Vec::new();
+
+    for s in ids.iter() {
+        let position = parse_id(s).and_then(|id| archive.iter().position(|e| e.id == id));
+        let Some(position) = position else {
+            not_found.push(s.clone());
+            continue;
+        };
+
+        let entry = archive.remove(position);
+        let mut task = entry.task;
+        task.id = storage::get_next_task_id(&tasks);
+        if reopen {
+            task.reopen();
+        }
+
+        // The tag may have been removed since the task was archived
+        if !tag_exists(&tags, &task.tag) {
+            tags.push(Tag::new(task.tag.clone()));
+        }
+
+        tasks.push(task.clone());
+        restored.push(RestoredEntry {
+            archive_id: entry.id,
+            task,
+        });
+    }
+
+    if !restored.is_empty() && !dry_run {
+        // Save tasks first so a failure never loses the restored entries
+        storage::save_tasks(&tasks)?;
+        storage::save_tags(&tags)?;
+        storage::save_archive(&archive)?;
+    }
+
+    let partial = !restored.is_empty();
+
+    if opts.output.is_structured() {
+        let result = RestoreResult {
+            command: "archive restore",
+            dry_run,
+            restored,
+            not_found: not_found.clone(),
+        };
+        output::print_result(opts.output, &result);
+    } else {
+        let verb = if dry_run { "Would restore" } else { "Restored" };
+        for entry in restored {
+            println!(
+                "{} task {:03}: {} ({})",
+                verb, entry.task.id, entry.task.name, entry.task.tag
+            );
+        }
+    }
+
+    if !not_found.is_empty() {
+        return Err(TaskError::NotFound {
+            ids: not_found,
+            partial,
+        }
+        .into());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Task;
+    use chrono::NaiveDate;
+
+    fn mk_entry(id: u32, name: &str, tag: &str) -> ArchivedTask {
+        let date = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+        let mut task = Task::new(id, name.to_string(), tag.to_string(), None);
+        task.completed = true;
+        ArchivedTask::new(id, date, task)
+    }
+
+    #[test]
+    fn test_matches_query_name_case_insensitive() {
+        let entry = mk_entry(1, "Rewatch Lecture 1", "Automata");
+        assert!(matches_query(&entry, "lecture"));
+        assert!(!matches_query(&entry, "homework"));
+    }
+
+    #[test]
+    fn test_matches_query_tag() {
+        let entry = mk_entry(1, "Homework", "Calculus");
+        assert!(matches_query(&entry, "calc"));
+    }
+
+    #[test]
+    fn test_format_entry() {
+        let entry = mk_entry(7, "Homework", "Calculus");
+        assert_eq!(
+            format_entry(&entry, "%d.%m.%Y"),
+            "007 - Homework (Calculus, no deadline, archived 01.03.2026)"
+        );
+        assert!(format_entry(&entry, "%Y-%m-%d").ends_with("archived 2026-03-01)"));
+    }
+}
@@ -0,0 +1,321 @@
+use crate::commands::clear::confirm;
+use crate::commands::GlobalOptions;
+use crate::error::TaskError;
+use crate::models::{Tag, Task};
+use crate::output;
+use crate::storage::{self, tag_exists, Backup};
+use anyhow::Result;
+use chrono::NaiveDateTime;
+use serde::Serialize;
+use std::io::{self, IsTerminal};
+
+/// A single backup in the structured result of `backup list`
+#[derive(Serialize)]
+struct BackupEntry {
+    stamp: String,
+    created: NaiveDateTime,
+    tasks: usize,
+}
+
+/// Structured result of `backup restore`
+#[derive(Serialize)]
+struct RestoreResult {
+    command: &'static str,
+    dry_run: bool,
+    restored: bool,
+    stamp: String,
+    added: Vec<u32>,
+    removed: Vec<u32>,
+    changed: Vec<u32>,
+}
+
+/// Differences between the current tasks and a backup, matched by task ID
+#[derive(Debug, Default)]
+struct Diff<'a> {
+    /// Tasks only in the backup
+    added: Vec<&'a Task>,
+    /// Tasks only in the current list
+    removed: Vec<&'a Task>,
+    /// Current and backed up versions of tasks that differ
+    changed: Vec<(&'a Task, &'a Task)>,
+}
+
+impl<'a> Diff<'a> {
+    /// Compare the current tasks with the tasks of a backup
+    fn new(current: &'a [Task], backup: &'a [Task]) -> Self {
+        let mut diff = Diff::default();
+        for old in backup {
+            match current.iter().find(|t| t.id == old.id) {
+                None => diff.added.push(old),
+                Some(task) if task != old => diff.changed.push((task, old)),
+                Some(_) => {}
+            }
+        }
+        diff.removed = current
+            .iter()
+            .filter(|t| !backup.iter().any(|old| old.id == t.id))
+            .collect();
+        diff.added.sort_by_key(|t| t.id);
+        diff.removed.sort_by_key(|t| t.id);
+        diff.changed.sort_by_key(|(t, _)| t.id);
+        diff
+    }
+
+    fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    /// One line per task: `+` for tasks the restore brings back, `-` for tasks it removes
+    /// and `~` with the changed fields for tasks it changes
+    fn lines(&self, date_format: &str) -> Vec<String> {
+        let row = |mark: char, task: &Task| {
+            format!("  {} {:03} - {} ({})", mark, task.id, task.name, task.tag)
+        };
+        let mut lines: Vec<String> = Vec::new();
+        lines.extend(self.added.iter().map(|t| row('+', t)));
+        lines.extend(self.removed.iter().map(|t| row('-', t)));
+        for (task, old) in &self.changed {
+            lines.push(format!(
+                "{}: {}",
+                row('~', task),
+                describe_changes(task, old, date_format).join(", ")
+            ));
+        }
+        lines
+    }
+}
+
+/// The fields that restoring `old` over `task` changes, e.g. `deadline 01.03.2026 -> none`
+fn describe_changes(task: &Task, old: &Task, date_format: &str) -> Vec<String> {
+    let deadline = |t: &Task| {
+        t.deadline
+            .map_or("none".to_string(), |d| d.format(date_format).to_string())
+    };
+    let state = |t: &Task| if t.completed { "done" } else { "open" };
+    let mut changes = Vec::new();
+    if task.name != old.name {
+        changes.push(format!("name \"{}\" -> \"{}\"", task.name, old.name));
+    }
+    if task.tag != old.tag {
+        changes.push(format!("tag {} -> {}", task.tag, old.tag));
+    }
+    if task.deadline != old.deadline {
+        changes.push(format!("deadline {} -> {}", deadline(task), deadline(old)));
+    }
+    if task.completed != old.completed {
+        changes.push(format!("{} -> {}", state(task), state(old)));
+    }
+    if task.priority != old.priority {
+        let priority = |t: &Task| t.priority.map_or("none".to_string(), |p| p.to_string());
+        changes.push(format!("priority {} -> {}", priority(task), priority(old)));
+    }
+    if changes.is_empty() {
+        changes.push("other details".to_string());
+    }
+    changes
+}
+
+/// Find the backup named by `stamp`, or by a prefix of exactly one stamp
+fn find_backup(backups: Vec<Backup>, stamp: &str) -> Result<Backup> {
+    if let Some(backup) = backups.iter().find(|b| b.stamp == stamp) {
+        return Ok(backup.clone());
+    }
+    let mut matches: Vec<Backup> = backups
+        .into_iter()
+        .filter(|b| !stamp.is_empty() && b.stamp.starts_with(stamp))
+        .collect();
+    match matches.len() {
+        0 => Err(TaskError::NotFound {
+            ids: vec![stamp.to_string()],
+            partial: false,
+        }
+        .into()),
+        1 => Ok(matches.remove(0)),
+        n => Err(TaskError::validation(format!(
+            "Backup \"{}\" is ambiguous ({} backups match)",
+            stamp, n
+        ))
+        .into()),
+    }
+}
+
+/// List the backups of the tasks file, oldest first, with the number of tasks in each
+pub fn list_backups(date_format: &str, opts: &GlobalOptions) -> Result<()> {
+    let backups = storage::list_backups()?;
+
+    if backups.is_empty() && !opts.output.is_structured() {
+        println!("No backups yet");
+        return Ok(());
+    }
+
+    let mut entries = Vec::new();
+    for backup in backups {
+        let tasks = storage::load_backup(&backup)?.len();
+        entries.push(BackupEntry {
+            stamp: backup.stamp,
+            created: backup.created,
+            tasks,
+        });
+    }
+
+    if opts.output.is_structured() {
+        output::print_records(opts.output, "backup list", "backups", &entries);
+        return Ok(());
+    }
+    for entry in entries {
+        println!(
+            "{}  {}  {} tasks",
+            entry.stamp,
+            entry.created.format(&format!("{} %H:%M:%S", date_format)),
+            entry.tasks
+        );
+    }
+    Ok(())
+}
+
+/// Replace the task list with a backup, after showing how it differs from the current
+/// tasks. Tags of restored tasks that no longer exist are created again. Saving makes a
+/// backup of the current tasks, so a restore can itself be undone.
+///
+/// With `dry_run` only the differences are printed. When stdout is a terminal the user is
+/// asked to confirm first, unless `assume_yes` is set.
+pub fn restore_backup(stamp: String, date_format: &str, opts: &GlobalOptions) -> Result<()> {
+    let structured = opts.output.is_structured();
+    let _lock = storage::lock_storage()?;
+    let backup = find_backup(storage::list_backups()?, &stamp)?;
+    let restored_tasks = storage::load_backup(&backup)?;
+    let current = storage::load_tasks()?;
+    let diff = Diff::new(&current, &restored_tasks);
+
+    let mut result = RestoreResult {
+        command: "backup restore",
+        dry_run: opts.dry_run,
+        restored: false,
+        stamp: backup.stamp.clone(),
+        added: diff.added.iter().map(|t| t.id).collect(),
+        removed: diff.removed.iter().map(|t| t.id).collect(),
+        changed: diff.changed.iter().map(|(t, _)| t.id).collect(),
+    };
+
+    if diff.is_empty() {
+        if structured {
+            output::print_result(opts.output, &result);
+        } else {
+            println!("Backup {} matches the current tasks", backup.stamp);
+        }
+        return Ok(());
+    }
+
+    let preview = format!(
+        "Restoring backup {} changes the current tasks:\n{}",
+        backup.stamp,
+        diff.lines(date_format).join("\n")
+    );
+    if structured {
+        eprintln!("{}", preview);
+    } else {
+        println!("{}", preview);
+    }
+
+    if opts.dry_run {
+        if structured {
+            output::print_result(opts.output, &result);
+        } else {
+            println!("Would restore backup {}", backup.stamp);
+        }
+        return Ok(());
+    }
+
+    if !opts.assume_yes && io::stdout().is_terminal() && !confirm("Proceed?", structured)? {
+        if structured {
+            output::print_result(opts.output, &result);
+        } else {
+            println!("Aborted, no tasks were changed");
+        }
+        return Ok(());
+    }
+
+    // The tags of restored tasks may have been removed since the backup
+    let mut tags = storage::load_tags()?;
+    let tag_count = tags.len();
+    for task in &restored_tasks {
+        if !tag_exists(&tags, &task.tag) {
+            tags.push(Tag::new(task.tag.clone()));
+        }
+    }
+
+    storage::save_tasks(&restored_tasks)?;
+    if tags.len() != tag_count {
+        storage::save_tags(&tags)?;
+    }
+    result.restored = true;
+
+    if structured {
+        output::print_result(opts.output, &result);
+    } else {
+        println!(
+            "Restored backup {} ({} tasks)",
+            backup.stamp,
+            restored_tasks.len()
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use std::path::PathBuf;
+
+    fn task(id: u32, name: &str) -> Task {
+        Task::new(
+            id,
+            name.to_string(),
+            "Physics".to_string(),
+            NaiveDate::from_ymd_opt(2026, 3, 1),
+        )
+    }
+
+    #[test]
+    fn test_diff_lines() {
+        let mut done = task(2, "Quiz");
+        done.completed = true;
+        done.deadline = None;
+        let current = vec![task(1, "Lab"), done, task(3, "Essay")];
+        let backup = vec![task(1, "Lab"), task(2, "Quiz"), task(4, "Reading")];
+
+        let diff = Diff::new(&current, &backup);
+        assert_eq!(
+            diff.lines("%d.%m.%Y"),
+            [
+                "  + 004 - Reading (Physics)",
+                "  - 003 - Essay (Physics)",
+                "  ~ 002 - Quiz (Physics): deadline none -> 01.03.2026, done -> open",
+            ]
+        );
+        assert!(Diff::new(&current, &current).is_empty());
+    }
+
+    #[test]
+    fn test_find_backup() {
+        let backup = |stamp: &str| Backup {
+            stamp: stamp.to_string(),
+            created: NaiveDateTime::parse_from_str(stamp, "%Y%m%d-%H%M%S%.3f").unwrap(),
+            path: PathBuf::from(format!("{}.json", stamp)),
+        };
+        let backups = vec![
+            backup("20261018-090000.000"),
+            backup("20261019-090000.000"),
+            backup("20261019-170000.000"),
+        ];
+
+        let found = find_backup(backups.clone(), "20261018").unwrap();
+        assert_eq!(found.stamp, "20261018-090000.000");
+        let found = find_backup(backups.clone(), "20261019-170000.000").unwrap();
+        assert_eq!(found.stamp, "20261019-170000.000");
+        let err = find_backup(backups.clone(), "20261019").unwrap_err();
+        assert!(err.to_string().contains("ambiguous (2 backups match)"));
+        assert!(find_backup(backups, "2025").is_err());
+    }
+}
@@ -0,0 +1,478 @@
+use crate::commands::add::parse_date;
+use crate::commands::GlobalOptions;
+use crate::config::Settings;
+use crate::error::TaskError;
+use crate::models::Task;
+use crate::output;
+use crate::storage;
+use crate::ui;
+use anyhow::Result;
+use chrono::{Duration, Local, NaiveDate};
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Rows of the terminal chart
+const CHART_HEIGHT: usize = 10;
+/// Chart columns when the terminal width is unknown
+const DEFAULT_CHART_WIDTH: usize = 60;
+/// Columns reserved for the y axis labels
+const CHART_LABEL_WIDTH: usize = 8;
+/// Days shown when no `--from` can be derived from the tasks
+const DEFAULT_DAYS: i64 = 28;
+/// Size of the SVG drawing area, in pixels
+const SVG_WIDTH: f64 = 640.0;
+const SVG_HEIGHT: f64 = 320.0;
+/// Space around the SVG drawing area for axes and labels
+const SVG_MARGIN: f64 = 48.0;
+
+/// One day of the burndown
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct BurndownDay {
+    date: NaiveDate,
+    /// Open tasks at the end of the day; None for days after today
+    remaining: Option<usize>,
+    /// Where the remaining count would be on a straight line to zero
+    ideal: f64,
+}
+
+/// Structured result of `burndown`
+#[derive(Serialize)]
+struct BurndownReport<'a> {
+    command: &'static str,
+    tag: Option<&'a str>,
+    from: NaiveDate,
+    to: NaiveDate,
+    svg: Option<&'a Path>,
+    days: &'a [BurndownDay],
+}
+
+/// Whether a task was open at the end of `day`. Tasks without `created_at` count as open
+/// from the start; completed tasks without `completed_at` never count as open.
+fn open_on(task: &Task, day: NaiveDate) -> bool {
+    if task.created_at.is_some_and(|at| at.date_naive() > day) {
+        return false;
+    }
+    match (task.completed, task.completed_at) {
+        (false, _) => true,
+        (true, Some(at)) => at.date_naive() > day,
+        (true, None) => false,
+    }
+}
+
+/// Remaining open tasks for every day of `from..=to`, up to `today`, with the ideal line
+/// from the first day's count down to zero on `to`
+fn burndown_days(
+    tasks: &[&Task],
+    from: NaiveDate,
+    to: NaiveDate,
+    today: NaiveDate,
+) -> Vec<BurndownDay> {
+    let span = (to - from).num_days().max(1) as f64;
+    let start = tasks.iter().filter(|t| open_on(t, from)).count() as f64;
+
+    let mut days = Vec::new();
+    let mut date = from;
+    while date <= to {
+        let elapsed = (date - from).num_days() as f64;
+        days.push(BurndownDay {
+            date,
+            remaining: (date <= today).then(|| tasks.iter().filter(|t| open_on(t, date)).count()),
+            ideal: start * (1.0 - elapsed / span),
+        });
+        date += Duration::days(1);
+    }
+    days
+}
+
+/// Draw the burndown as text: a bar per column for the remaining tasks and `·` for the
+/// ideal line, with the first and last date below. With more days than `width`, columns
+/// sample evenly spaced days.
+fn render_chart(days: &[BurndownDay], width: usize, date_format: &str) -> Vec<String> {
+    let max = days
+        .iter()
+        .map(|d| d.remaining.unwrap_or(0).max(d.ideal.ceil() as usize))
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    let columns = width.clamp(1, days.len().max(1));
+    let sampled: Vec<&BurndownDay> = (0..columns)
+        .map(|c| match columns {
+            1 => &days[0],
+            _ => &days[c * (days.len() - 1) / (columns - 1)],
+        })
+        .collect();
+    let scale = |v: f64| (v * CHART_HEIGHT as f64 / max as f64).round() as usize;
+
+    let label_width = max.to_string().len();
+    let mut lines = Vec::new();
+    for row in (1..=CHART_HEIGHT).rev() {
+        let label = if row == CHART_HEIGHT {
+            max.to_string()
+        } else {
+            String::new()
+        };
+        let cells: String = sampled
+            .iter()
+            .map(|day| {
+                if scale(day.ideal) == row {
+                    '·'
+                } else if day.remaining.is_some_and(|r| scale(r as f64) >= row) {
+                    '█'
+                } else {
+                    ' '
+                }
+            })
+            .collect();
+        lines.push(format!("{:>w$} ┤{}", label, cells, w = label_width));
+    }
+    lines.push(format!(
+        "{:>w$} └{}",
+        0,
+        "─".repeat(sampled.len()),
+        w = label_width
+    ));
+
+    let first = days.first().map(|d| d.date.format(date_format).to_string());
+    let last = days.last().map(|d| d.date.format(date_format).to_string());
+    let (first, last) = (first.unwrap_or_default(), last.unwrap_or_default());
+    let gap = sampled
+        .len()
+        .saturating_sub(first.len() + last.len())
+        .max(1);
+    lines.push(format!(
+        "{}{}{}{}",
+        " ".repeat(label_width + 2),
+        first,
+        " ".repeat(gap),
+        last
+    ));
+    lines
+}
+
+/// Draw the burndown as a standalone SVG document: the remaining tasks as a solid line,
+/// the ideal line dashed
+fn render_svg(days: &[BurndownDay], title: &str, date_format: &str) -> String {
+    let max = days
+        .iter()
+        .map(|d| (d.remaining.unwrap_or(0) as f64).max(d.ideal))
+        .fold(1.0, f64::max);
+    let span = (days.len().max(2) - 1) as f64;
+    let x = |i: usize| SVG_MARGIN + i as f64 * SVG_WIDTH / span;
+    let y = |v: f64| SVG_MARGIN + SVG_HEIGHT - v * SVG_HEIGHT / max;
+    let points = |values: Vec<(usize, f64)>| {
+        values
+            .iter()
+            .map(|&(i, v)| format!("{:.1},{:.1}", x(i), y(v)))
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+
+    let actual = points(
+        days.iter()
+            .enumerate()
+            .filter_map(|(i, d)| Some((i, d.remaining? as f64)))
+            .collect(),
+    );
+    let ideal = points(days.iter().enumerate().map(|(i, d)| (i, d.ideal)).collect());
+    let (first, last) = (days.first(), days.last());
+    let date = |d: Option<&BurndownDay>| {
+        d.map(|d| d.date.format(date_format).to_string())
+            .unwrap_or_default()
+    };
+
+    let total_width = SVG_WIDTH + 2.0 * SVG_MARGIN;
+    let total_height = SVG_HEIGHT + 2.0 * SVG_MARGIN;
+    let bottom = SVG_MARGIN + SVG_HEIGHT;
+    let right = SVG_MARGIN + SVG_WIDTH;
+    let mut svg = String::new();
+    svg.push_str(&format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\" font-family=\"sans-serif\" font-size=\"12\">\n",
+        w = total_width,
+        h = total_height
+    ));
+    svg.push_str(&format!(
+        "  <text x=\"{}\" y=\"{}\" font-size=\"14\">{}</text>\n",
+        SVG_MARGIN,
+        SVG_MARGIN / 2.0,
+        escape_xml(title)
+    ));
+    svg.push_str(&format!(
+        "  <path d=\"M{m},{m} V{b} H{r}\" fill=\"none\" stroke=\"black\"/>\n",
+        m = SVG_MARGIN,
+        b = bottom,
+        r = right
+    ));
+    svg.push_str(&format!(
+        "  <text x=\"{}\" y=\"{}\" text-anchor=\"end\">{}</text>\n",
+        SVG_MARGIN - 6.0,
+        SVG_MARGIN + 4.0,
+        max.ceil()
+    ));
+    svg.push_str(&format!(
+        "  <text x=\"{}\" y=\"{}\" text-anchor=\"end\">0</text>\n",
+        SVG_MARGIN - 6.0,
+        bottom + 4.0
+    ));
+    svg.push_str(&format!(
+        "  <text x=\"{}\" y=\"{}\">{}</text>\n",
+        SVG_MARGIN,
+        bottom + 20.0,
+        date(first)
+    ));
+    svg.push_str(&format!(
+        "  <text x=\"{}\" y=\"{}\" text-anchor=\"end\">{}</text>\n",
+        right,
+        bottom + 20.0,
+        date(last)
+    ));
+    svg.push_str(&format!(
+        "  <polyline points=\"{}\" fill=\"none\" stroke=\"gray\" stroke-dasharray=\"6 4\"/>\n",
+        ideal
+    ));
+    if !actual.is_empty() {
+        svg.push_str(&format!(
+            "  <polyline points=\"{}\" fill=\"none\" stroke=\"steelblue\" stroke-width=\"2\"/>\n",
+            actual
+        ));
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Escape text for use in XML content
+pub(crate) fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Plot the open tasks of a tag (or all tasks) per day from `from` to `to`, reconstructed
+/// from the creation and completion timestamps, against a straight line to zero.
+/// `from` defaults to the earliest creation, `to` to the latest deadline or today.
+/// With `svg` the chart is also written to that file.
+pub fn show_burndown(
+    tag: Option<String>,
+    from: Option<String>,
+    to: Option<String>,
+    svg: Option<PathBuf>,
+    settings: &Settings,
+    opts: &GlobalOptions,
+) -> Result<()> {
+    let today = Local::now().date_naive();
+    let tasks = storage::load_tasks()?;
+    let archive = storage::load_archive()?;
+    let selected: Vec<&Task> = tasks
+        .iter()
+        .chain(archive.iter().map(|e| &e.task))
+        .filter(|t| tag.as_ref().is_none_or(|tag| &t.tag == tag))
+        .collect();
+    if let Some(tag) = &tag {
+        if selected.is_empty() {
+            return Err(TaskError::validation(format!("No tasks with tag: {}", tag)).into());
+        }
+    }
+
+    let input_format = settings.date_input_format.as_deref();
+    let from_arg = from.map(|s| parse_date(&s, input_format)).transpose()?;
+    let to_arg = to.map(|s| parse_date(&s, input_format)).transpose()?;
+    if let (Some(from), Some(to)) = (from_arg, to_arg) {
+        if from >= to {
+            return Err(TaskError::validation("--from must be before --to").into());
+        }
+    }
+
+    // A default end never falls on or before the start, and a default start never on or
+    // after the end, e.g. for tasks created today without deadlines
+    let to = to_arg.unwrap_or_else(|| {
+        let latest = selected
+            .iter()
+            .filter_map(|t| t.deadline)
+            .max()
+            .map_or(today, |d| d.max(today));
+        match from_arg {
+            Some(from) if from >= latest => from + Duration::days(DEFAULT_DAYS),
+            _ => latest,
+        }
+    });
+    let from = from_arg.unwrap_or_else(|| {
+        let earliest = selected
+            .iter()
+            .filter_map(|t| t.created_at.map(|at| at.date_naive()))
+            .min()
+            .unwrap_or(today - Duration::days(DEFAULT_DAYS));
+        if earliest >= to {
+            to - Duration::days(DEFAULT_DAYS)
+        } else {
+            earliest
+        }
+    });
+
+    let days = burndown_days(&selected, from, to, today);
+    let date_format = &settings.date_output_format;
+    let title = format!(
+        "Burndown {}{} - {}",
+        tag.as_ref().map_or(String::new(), |t| format!("{} ", t)),
+        from.format(date_format),
+        to.format(date_format)
+    );
+
+    if let Some(path) = &svg {
+        if !opts.dry_run {
+            fs::write(path, render_svg(&days, &title, date_format))
+                .map_err(|e| TaskError::storage("Failed to write SVG file", e))?;
+        }
+    }
+
+    if opts.output.is_structured() {
+        let report = BurndownReport {
+            command: "burndown",
+            tag: tag.as_deref(),
+            from,
+            to,
+            svg: svg.as_deref(),
+            days: &days,
+        };
+        output::print_result(opts.output, &report);
+        return Ok(());
+    }
+
+    let color = opts.color.enabled();
+    if color {
+        println!("{}", settings.theme.header.paint(&title));
+    } else {
+        println!("{}", title);
+    }
+
+    // Leave room for the axis labels
+    let width = ui::terminal_width()
+        .map(|w| w.saturating_sub(CHART_LABEL_WIDTH))
+        .unwrap_or(DEFAULT_CHART_WIDTH);
+    for line in render_chart(&days, width, date_format) {
+        println!("{}", line);
+    }
+
+    if let Some(now) = days.iter().rev().find(|d| d.remaining.is_some()) {
+        let remaining = now.remaining.unwrap_or(0);
+        let ideal = now.ideal.round() as usize;
+        let verdict = match remaining.cmp(&ideal) {
+            std::cmp::Ordering::Greater => format!("behind by {}", remaining - ideal),
+            std::cmp::Ordering::Less => format!("ahead by {}", ideal - remaining),
+            std::cmp::Ordering::Equal => "on track".to_string(),
+        };
+        let line = format!(
+            "{} open on {}, ideal {}: {}",
+            remaining,
+            now.date.format(date_format),
+            ideal,
+            verdict
+        );
+        if color && remaining > ideal {
+            println!("{}", settings.theme.overdue.paint(&line));
+        } else {
+            println!("{}", line);
+        }
+    }
+
+    if let Some(path) = &svg {
+        let verb = if opts.dry_run { "Would write" } else { "Wrote" };
+        println!("{} {}", verb, path.display());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::testing::date;
+    use chrono::TimeZone;
+
+    fn task(id: u32, created: Option<u32>, completed: Option<u32>) -> Task {
+        let at = |d: u32| {
+            Local
+                .from_local_datetime(&date(d).and_hms_opt(12, 0, 0).unwrap())
+                .single()
+                .unwrap()
+        };
+        let mut task = Task::new(id, format!("T{}", id), "Lab".to_string(), None);
+        task.created_at = created.map(at);
+        if let Some(d) = completed {
+            task.complete(at(d));
+        }
+        task
+    }
+
+    #[test]
+    fn test_open_on() {
+        assert!(!open_on(&task(1, Some(5), None), date(4)));
+        assert!(open_on(&task(1, Some(5), None), date(5)));
+        assert!(open_on(&task(1, None, Some(8)), date(7)));
+        assert!(!open_on(&task(1, None, Some(8)), date(8)));
+
+        let mut done = task(1, None, None);
+        done.completed = true;
+        assert!(!open_on(&done, date(1)));
+    }
+
+    #[test]
+    fn test_burndown_days() {
+        let tasks = [
+            task(1, None, Some(2)),
+            task(2, None, Some(4)),
+            task(3, Some(3), None),
+            task(4, None, None),
+        ];
+        let refs: Vec<&Task> = tasks.iter().collect();
+
+        let days = burndown_days(&refs, date(1), date(5), date(4));
+        let remaining: Vec<Option<usize>> = days.iter().map(|d| d.remaining).collect();
+        assert_eq!(remaining, vec![Some(3), Some(2), Some(3), Some(2), None]);
+        let ideal: Vec<f64> = days.iter().map(|d| d.ideal).collect();
+        assert_eq!(ideal, vec![3.0, 2.25, 1.5, 0.75, 0.0]);
+    }
+
+    #[test]
+    fn test_render_chart() {
+        let days: Vec<BurndownDay> = (0..3)
+            .map(|i| BurndownDay {
+                date: date(1 + i),
+                remaining: Some(10 - 5 * i as usize),
+                ideal: 10.0 - 5.0 * i as f64,
+            })
+            .collect();
+
+        let chart = render_chart(&days, 80, "%d");
+        assert_eq!(chart.len(), CHART_HEIGHT + 2);
+        assert_eq!(chart[0], "10 ┤·  ");
+        assert_eq!(chart[5], "   ┤█· ");
+        assert_eq!(chart[9], "   ┤██ ");
+        assert_eq!(chart[10], " 0 └───");
+        assert_eq!(chart[11], "    01 03");
+
+        // Wide ranges are sampled down to the width
+        assert_eq!(render_chart(&days, 2, "%d")[10], " 0 └──");
+    }
+
+    #[test]
+    fn test_render_svg() {
+        let days = vec![
+            BurndownDay {
+                date: date(1),
+                remaining: Some(2),
+                ideal: 2.0,
+            },
+            BurndownDay {
+                date: date(2),
+                remaining: None,
+                ideal: 0.0,
+            },
+        ];
+        let svg = render_svg(&days, "Burndown <Lab>", "%d.%m.%Y");
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.contains("Burndown &lt;Lab&gt;"));
+        assert!(svg.contains("stroke-dasharray"));
+        assert!(svg.contains("01.02.2026"));
+        assert!(svg.trim_end().ends_with("</svg>"));
+    }
+}
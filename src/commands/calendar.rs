@@ -0,0 +1,280 @@
+use crate::commands::GlobalOptions;
+use crate::config::Settings;
+use crate::error::TaskError;
+use crate::models::Task;
+use crate::output;
+use crate::storage;
+use crate::theme::Theme;
+use crate::ui;
+use anyhow::Result;
+use chrono::{Datelike, Duration, Local, NaiveDate};
+use comfy_table::{presets, Cell, ColumnConstraint, Table, Width};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Cell content width when the terminal width is unknown
+const DEFAULT_CELL_WIDTH: usize = 12;
+/// Cells narrower than this show only task counts, no names
+const MIN_NAMES_WIDTH: usize = 8;
+/// Most names listed in a month cell before "+N more"
+const MONTH_NAMES: usize = 3;
+/// Most names listed in a week cell before "+N more"
+const WEEK_NAMES: usize = 10;
+/// ANSI SGR code for reverse video, used to highlight today
+const REVERSE: &str = "7";
+
+/// A day with deadlines in the structured result of `calendar`
+#[derive(Serialize)]
+struct CalendarDay<'a> {
+    date: NaiveDate,
+    tasks: Vec<&'a Task>,
+}
+
+/// Parse a `--month` value like "2026-03" into the first day of that month
+fn parse_month(s: &str) -> Result<NaiveDate> {
+    NaiveDate::parse_from_str(&format!("{}-01", s.trim()), "%Y-%m-%d").map_err(|_| {
+        TaskError::validation(format!("Invalid month \"{}\" (expected YYYY-MM)", s)).into()
+    })
+}
+
+/// Monday of the week containing `date`
+fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+/// Weeks (Monday to Sunday) to draw for a month. Days outside the month are None.
+fn month_weeks(first: NaiveDate) -> Vec<[Option<NaiveDate>; 7]> {
+    let mut weeks = Vec::new();
+    let mut monday = week_start(first);
+    while monday <= first || monday.month() == first.month() {
+        let mut week = [None; 7];
+        for (i, cell) in week.iter_mut().enumerate() {
+            let day = monday + Duration::days(i as i64);
+            if day.month() == first.month() {
+                *cell = Some(day);
+            }
+        }
+        weeks.push(week);
+        monday += Duration::days(7);
+    }
+    weeks
+}
+
+/// Width of each cell's content so that seven columns fit the terminal.
+/// Each column also takes two columns of padding and one border.
+fn cell_width(term_width: Option<usize>) -> usize {
+    match term_width {
+        Some(w) => (w.saturating_sub(1) / 7).saturating_sub(3).clamp(2, 24),
+        None => DEFAULT_CELL_WIDTH,
+    }
+}
+
+/// Lines of one calendar cell: the day number (with the task count), then task names.
+/// Today is shown in reverse video, or bracketed when styling is off. Days before today
+/// with open tasks get a `!`, and the overdue style with styling on.
+fn cell_lines(
+    day: NaiveDate,
+    tasks: &[&Task],
+    today: NaiveDate,
+    width: usize,
+    max_names: usize,
+    style: Option<&Theme>,
+) -> Vec<String> {
+    let mut header = match (day == today, style) {
+        (true, Some(_)) => ui::paint(REVERSE, &day.day().to_string()),
+        (true, None) => format!("[{}]", day.day()),
+        (false, _) => day.day().to_string(),
+    };
+    if !tasks.is_empty() {
+        header.push_str(&format!(" ({})", tasks.len()));
+        if day < today {
+            header.push('!');
+            if let Some(theme) = style {
+                header = theme.overdue.paint(&header);
+            }
+        }
+    }
+
+    let mut lines = vec![header];
+    if width < MIN_NAMES_WIDTH {
+        return lines;
+    }
+
+    let shown = if tasks.len() > max_names {
+        max_names.saturating_sub(1)
+    } else {
+        tasks.len()
+    };
+    for task in &tasks[..shown] {
+        lines.push(ui::truncate_to_width(&task.name, width));
+    }
+    if shown < tasks.len() {
+        lines.push(format!("+{} more", tasks.len() - shown));
+    }
+    lines
+}
+
+/// Draw a calendar grid of a month, or of the current week with `week`, with active tasks
+/// placed on their deadline. Today is highlighted; past days with open tasks are marked
+/// overdue.
+pub fn show_calendar(
+    month: Option<String>,
+    week: bool,
+    settings: &Settings,
+    opts: &GlobalOptions,
+) -> Result<()> {
+    let today = Local::now().date_naive();
+    let first = match &month {
+        Some(m) => parse_month(m)?,
+        None => today.with_day(1).expect("every month has a first day"),
+    };
+
+    let (title, weeks) = if week {
+        let monday = week_start(today);
+        let days: [Option<NaiveDate>; 7] =
+            std::array::from_fn(|i| Some(monday + Duration::days(i as i64)));
+        let sunday = monday + Duration::days(6);
+        let title = format!(
+            "Week {}, {} - {}",
+            monday.iso_week().week(),
+            monday.format(&settings.date_output_format),
+            sunday.format(&settings.date_output_format)
+        );
+        (title, vec![days])
+    } else {
+        (first.format("%B %Y").to_string(), month_weeks(first))
+    };
+
+    let tasks = storage::load_tasks()?;
+    let mut by_day: BTreeMap<NaiveDate, Vec<&Task>> = BTreeMap::new();
+    for task in tasks.iter().filter(|t| !t.completed) {
+        if let Some(deadline) = task.deadline {
+            by_day.entry(deadline).or_default().push(task);
+        }
+    }
+    let shown_days: Vec<NaiveDate> = weeks.iter().flatten().flatten().copied().collect();
+    by_day.retain(|day, _| shown_days.contains(day));
+
+    if opts.output.is_structured() {
+        let days: Vec<CalendarDay> = by_day
+            .into_iter()
+            .map(|(date, mut tasks)| {
+                tasks.sort_by_key(|t| t.id);
+                CalendarDay { date, tasks }
+            })
+            .collect();
+        output::print_records(opts.output, "calendar", "days", &days);
+        return Ok(());
+    }
+
+    let theme = &settings.theme;
+    let color = opts.color.enabled();
+    let width = cell_width(ui::terminal_width());
+    let max_names = if week { WEEK_NAMES } else { MONTH_NAMES };
+
+    let mut table = Table::new();
+    table.load_preset(presets::UTF8_FULL).force_no_tty();
+    table.set_header(["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"].map(|d| {
+        let d = ui::truncate_to_width(d, width);
+        if color {
+            theme.header.paint(&d)
+        } else {
+            d
+        }
+    }));
+
+    for days in &weeks {
+        table.add_row(days.iter().map(|day| {
+            let Some(day) = *day else {
+                return Cell::new("");
+            };
+            let mut tasks = by_day.get(&day).cloned().unwrap_or_default();
+            tasks.sort_by_key(|t| t.id);
+
+            let style = if color { Some(theme) } else { None };
+            Cell::new(cell_lines(day, &tasks, today, width, max_names, style).join("\n"))
+        }));
+    }
+    for column in table.column_iter_mut() {
+        column.set_constraint(ColumnConstraint::Absolute(Width::Fixed(width as u16 + 2)));
+    }
+
+    if color {
+        println!("{}", theme.header.paint(&title));
+    } else {
+        println!("{}", title);
+    }
+    println!("{}", table);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_parse_month() {
+        assert_eq!(parse_month("2026-03").unwrap(), date(2026, 3, 1));
+        assert!(parse_month("2026-13").is_err());
+        assert!(parse_month("March").is_err());
+    }
+
+    #[test]
+    fn test_month_weeks() {
+        // March 2026 starts on a Sunday and ends on a Tuesday
+        let weeks = month_weeks(date(2026, 3, 1));
+        assert_eq!(weeks.len(), 6);
+        assert_eq!(weeks[0][..6], [None; 6]);
+        assert_eq!(weeks[0][6], Some(date(2026, 3, 1)));
+        assert_eq!(weeks[5][1], Some(date(2026, 3, 31)));
+        assert_eq!(weeks[5][2], None);
+
+        // February 2021 fills exactly four weeks
+        assert_eq!(month_weeks(date(2021, 2, 1)).len(), 4);
+    }
+
+    #[test]
+    fn test_cell_lines() {
+        let today = date(2026, 2, 17);
+        let tasks: Vec<Task> = (1..=4)
+            .map(|i| Task::new(i, format!("Task number {}", i), "T".to_string(), None))
+            .collect();
+        let refs: Vec<&Task> = tasks.iter().collect();
+
+        assert_eq!(cell_lines(today, &[], today, 12, 3, None), vec!["[17]"]);
+        assert_eq!(
+            cell_lines(date(2026, 2, 16), &refs[..1], today, 12, 3, None),
+            vec!["16 (1)!", "Task number…"]
+        );
+        assert_eq!(
+            cell_lines(date(2026, 2, 20), &refs, today, 12, 3, None),
+            vec!["20 (4)", "Task number…", "Task number…", "+2 more"]
+        );
+        let theme = Theme::default();
+        assert_eq!(
+            cell_lines(today, &[], today, 12, 3, Some(&theme)),
+            vec!["\x1b[7m17\x1b[0m"]
+        );
+        assert_eq!(
+            cell_lines(date(2026, 2, 16), &refs[..1], today, 12, 3, Some(&theme))[0],
+            theme.overdue.paint("16 (1)!")
+        );
+        // Narrow cells only show the count
+        assert_eq!(
+            cell_lines(date(2026, 2, 20), &refs, today, 6, 3, None),
+            vec!["20 (4)"]
+        );
+    }
+
+    #[test]
+    fn test_cell_width() {
+        assert_eq!(cell_width(None), DEFAULT_CELL_WIDTH);
+        assert_eq!(cell_width(Some(80)), 8);
+        assert_eq!(cell_width(Some(20)), 2);
+    }
+}
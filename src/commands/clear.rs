@@ -1,34 +1,166 @@
+use crate::commands::GlobalOptions;
+use crate::models::{ArchivedTask, Task};
+use crate::output;
 use crate::storage;
-use anyhow::Result;
+use anyhow::{Context, Result};
+use chrono::Local;
+use serde::Serialize;
+use std::io::{self, BufRead, IsTerminal, Write};
+
+/// Structured result of `clear`
+#[derive(Serialize)]
+struct ClearResult {
+    command: &'static str,
+    dry_run: bool,
+    purged: bool,
+    removed: usize,
+    ids: Vec<u32>,
+}
+
+/// Interpret a confirmation answer. Only an explicit "y" or "yes" counts as consent.
+fn is_confirmed(answer: &str) -> bool {
+    matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
+/// Ask the user to confirm on stdin. Returns false on an empty or negative answer.
+/// The prompt goes to stderr when stdout carries structured output.
+pub(crate) fn confirm(prompt: &str, structured: bool) -> Result<bool> {
+    if structured {
+        eprint!("{} [y/N] ", prompt);
+        io::stderr().flush().context("Failed to write prompt")?;
+    } else {
+        print!("{} [y/N] ", prompt);
+        io::stdout().flush().context("Failed to write prompt")?;
+    }
+
+    let mut answer = String::new();
+    io::stdin()
+        .lock()
+        .read_line(&mut answer)
+        .context("Failed to read confirmation")?;
+    Ok(is_confirmed(&answer))
+}
+
+/// Format the tasks affected by a clear under a heading, one indented row per task
+fn describe_affected(heading: &str, tasks: &[Task]) -> String {
+    let mut text = heading.to_string();
+    for task in tasks {
+        text.push_str(&format!("\n    {:03} - {}", task.id, task.name));
+    }
+    text
+}
+
+/// Remove all tasks marked as completed from the active list.
+/// Completed tasks are moved to the archive unless `purge` is set, in which case
+/// they are deleted permanently.
+///
+/// With `dry_run` the affected tasks are printed and nothing is saved. When stdout is a
+/// terminal the user is asked to confirm first, unless `assume_yes` is set.
+pub fn clear_tasks(purge: bool, opts: &GlobalOptions) -> Result<()> {
+    let structured = opts.output.is_structured();
+    let _lock = storage::lock_storage()?;
+    let tasks = storage::load_tasks()?;
+
+    let (completed, remaining): (Vec<_>, Vec<_>) = tasks.into_iter().partition(|t| t.completed);
+
+    let mut result = ClearResult {
+        command: "clear",
+        dry_run: opts.dry_run,
+        purged: purge,
+        removed: 0,
+        ids: Vec::new(),
+    };
+
+    if completed.is_empty() {
+        if structured {
+            output::print_result(opts.output, &result);
+        } else {
+            println!("No completed tasks to clear");
+        }
+        return Ok(());
+    }
 
-/// Permanently delete all tasks marked as completed
-pub fn clear_tasks() -> Result<()> {
-    let mut tasks = storage::load_tasks()?;
+    let count = completed.len();
+    let action = if purge {
+        "permanently deleted"
+    } else {
+        "moved to archive"
+    };
+
+    if !opts.dry_run && !opts.assume_yes && io::stdout().is_terminal() {
+        let heading = format!("The following completed tasks will be {}:", action);
+        let preview = describe_affected(&heading, &completed);
+        if structured {
+            eprintln!("{}", preview);
+        } else {
+            println!("{}", preview);
+        }
+        if !confirm("Proceed?", structured)? {
+            if structured {
+                output::print_result(opts.output, &result);
+            } else {
+                println!("Aborted, no tasks were cleared");
+            }
+            return Ok(());
+        }
+    }
 
-    let original_count = tasks.len();
-    tasks.retain(|t| !t.completed);
-    let new_count = tasks.len();
+    result.removed = count;
+    result.ids = completed.iter().map(|t| t.id).collect();
 
-    if new_count == original_count {
-        println!("No completed tasks to clear");
+    if opts.dry_run {
+        if structured {
+            output::print_result(opts.output, &result);
+        } else {
+            let heading = format!("Would clear {} completed tasks ({}):", count, action);
+            println!("{}", describe_affected(&heading, &completed));
+        }
         return Ok(());
     }
 
-    storage::save_tasks(&tasks)?;
-    println!("Cleared {} completed tasks", original_count - new_count);
+    if !purge {
+        // Save the archive first so a failure never loses completed tasks
+        let mut archive = storage::load_archive()?;
+        let today = Local::now().date_naive();
+        for task in completed {
+            let id = storage::get_next_archive_id(&archive);
+            archive.push(ArchivedTask::new(id, today, task));
+        }
+        storage::save_archive(&archive)?;
+    }
+
+    storage::save_tasks(&remaining)?;
+
+    if structured {
+        output::print_result(opts.output, &result);
+    } else {
+        println!("Cleared {} completed tasks ({})", count, action);
+    }
 
     Ok(())
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::models::Task;
+    use super::*;
+
+    #[test]
+    fn test_is_confirmed() {
+        assert!(is_confirmed("y\n"));
+        assert!(is_confirmed("YES"));
+        assert!(!is_confirmed("\n"));
+        assert!(!is_confirmed("n"));
+        assert!(!is_confirmed("yep"));
+    }
 
     #[test]
     fn test_clear_no_completed_trivial() {
-        // trivial sanity check: function should run when tasks vector is empty
+        // trivial sanity check: partitioning an empty task list yields nothing to clear
         // We don't call clear_tasks() here because it interacts with filesystem storage
-        assert!(true);
+        let tasks: Vec<Task> = Vec::new();
+        let (completed, remaining): (Vec<_>, Vec<_>) = tasks.into_iter().partition(|t| t.completed);
+        assert!(completed.is_empty());
+        assert!(remaining.is_empty());
     }
 
     #[test]
@@ -0,0 +1,102 @@
+use crate::commands::GlobalOptions;
+use crate::config::{self, Settings};
+use crate::error::TaskError;
+use crate::output;
+use anyhow::Result;
+use serde::Serialize;
+
+/// A single setting in the structured result of `config get` and `config list`
+#[derive(Serialize)]
+struct SettingRecord {
+    key: String,
+    value: String,
+    source: &'static str,
+}
+
+/// Structured result of `config set`
+#[derive(Serialize)]
+struct SetResult<'a> {
+    command: &'static str,
+    dry_run: bool,
+    key: &'a str,
+    value: &'a str,
+    path: String,
+}
+
+/// Print the effective value of a single key.
+/// Fails with a validation error if the key is unknown, and with not found if it is unset
+/// without a built-in default.
+pub fn get_value(key: String, settings: &Settings, opts: &GlobalOptions) -> Result<()> {
+    let Some(value) = settings.get(&key)? else {
+        return Err(TaskError::NotFound {
+            ids: vec![key],
+            partial: false,
+        }
+        .into());
+    };
+
+    if opts.output.is_structured() {
+        let record = SettingRecord {
+            source: settings.source(&key).as_str(),
+            key,
+            value,
+        };
+        output::print_result(opts.output, &record);
+        return Ok(());
+    }
+
+    println!("{}", value);
+    Ok(())
+}
+
+/// Print every setting that has a value, with where the value comes from
+pub fn list_values(settings: &Settings, opts: &GlobalOptions) -> Result<()> {
+    let records: Vec<SettingRecord> = settings
+        .entries()
+        .into_iter()
+        .map(|(key, value, source)| SettingRecord {
+            key,
+            value,
+            source: source.as_str(),
+        })
+        .collect();
+
+    if opts.output.is_structured() {
+        output::print_records(opts.output, "config list", "settings", &records);
+        return Ok(());
+    }
+
+    let key_width = records.iter().map(|r| r.key.len()).max().unwrap_or(0);
+    for record in records {
+        println!(
+            "{:width$} = {}  ({})",
+            record.key,
+            record.value,
+            record.source,
+            width = key_width
+        );
+    }
+    Ok(())
+}
+
+/// Set a key in the config file. The new value is validated first, and nothing
+/// is written if it is invalid. With `dry_run` the file is left untouched.
+pub fn set_value(key: String, value: String, opts: &GlobalOptions) -> Result<()> {
+    let path = config::set(&key, &value, opts.dry_run)?;
+
+    if opts.output.is_structured() {
+        let result = SetResult {
+            command: "config set",
+            dry_run: opts.dry_run,
+            key: &key,
+            value: &value,
+            path: path.display().to_string(),
+        };
+        output::print_result(opts.output, &result);
+        return Ok(());
+    }
+
+    let verb = if opts.dry_run { "Would set" } else { "Set" };
+    println!("{} {} = {} in {}", verb, key, value, path.display());
+    Ok(())
+}
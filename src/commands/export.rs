@@ -0,0 +1,95 @@
+use crate::commands::GlobalOptions;
+use crate::config::Settings;
+use crate::error::TaskError;
+use crate::filter::TaskFilter;
+use crate::formats::{self, ExchangeFormat, ExportOptions};
+use crate::output;
+use crate::storage;
+use anyhow::Result;
+use chrono::Local;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Structured result of `export` to a file
+#[derive(Serialize)]
+struct ExportResult<'a> {
+    command: &'static str,
+    dry_run: bool,
+    format: &'static str,
+    file: &'a Path,
+    exported: usize,
+}
+
+/// Export active-list tasks matching all `filters`, ordered by ID, to `file` or stdout.
+/// For formats that sync by UID, exported tasks without a UID get one and are saved, so the
+/// next export of the same task updates its calendar entry instead of adding another.
+/// `ics_events` writes all-day events instead of to-dos.
+/// With `dry_run` nothing is written to the file and no UIDs are assigned.
+pub fn export_tasks(
+    format: ExchangeFormat,
+    filters: Vec<String>,
+    file: Option<PathBuf>,
+    ics_events: bool,
+    settings: &Settings,
+    opts: &GlobalOptions,
+) -> Result<()> {
+    if ics_events && format != ExchangeFormat::Ics {
+        return Err(TaskError::validation("--events only applies to --format ics").into());
+    }
+    let filter = TaskFilter::parse(&filters, settings.date_input_format.as_deref())?;
+    let options = ExportOptions {
+        ics_events,
+        now: Local::now(),
+        date_output_format: &settings.date_output_format,
+        sort: settings.sort,
+        urgency: settings.urgency.clone(),
+    };
+    let today = options.now.date_naive();
+
+    let mut tasks = if format.syncs_uids() && !opts.dry_run {
+        let _lock = storage::lock_storage()?;
+        let mut tasks = storage::load_tasks()?;
+        let matching = tasks.iter_mut().filter(|t| filter.matches(t, today));
+        if formats::assign_uids(matching) {
+            storage::save_tasks(&tasks)?;
+        }
+        tasks
+    } else {
+        storage::load_tasks()?
+    };
+    tasks.retain(|t| filter.matches(t, today));
+    tasks.sort_by_key(|t| t.id);
+
+    let content = formats::export(format, &tasks, &options)?;
+    let Some(file) = file else {
+        print!("{}", content);
+        return Ok(());
+    };
+
+    if !opts.dry_run {
+        fs::write(&file, content)
+            .map_err(|e| TaskError::storage(format!("Failed to write {}", file.display()), e))?;
+    }
+
+    if opts.output.is_structured() {
+        let result = ExportResult {
+            command: "export",
+            dry_run: opts.dry_run,
+            format: format.as_str(),
+            file: &file,
+            exported: tasks.len(),
+        };
+        output::print_result(opts.output, &result);
+        return Ok(());
+    }
+
+    let verb = if opts.dry_run {
+        "Would export"
+    } else {
+        "Exported"
+    };
+    let noun = if tasks.len() == 1 { "task" } else { "tasks" };
+    println!("{} {} {} to {}", verb, tasks.len(), noun, file.display());
+    Ok(())
+}
@@ -0,0 +1,320 @@
+use crate::commands::GlobalOptions;
+use crate::config::Settings;
+use crate::error::TaskError;
+use crate::formats::{self, ExchangeFormat, IcsTagSource, ImportOptions, FIELDS};
+use crate::models::{Tag, Task};
+use crate::output;
+use crate::storage::{self, tag_exists};
+use anyhow::Result;
+use chrono::{Local, NaiveDate};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// A record that could not be imported
+#[derive(Serialize)]
+struct RowError {
+    row: usize,
+    message: String,
+}
+
+/// One change a re-imported record made to an existing task
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(tag = "field", rename_all = "snake_case")]
+enum Change {
+    Deadline {
+        old: Option<NaiveDate>,
+        new: Option<NaiveDate>,
+    },
+    Name {
+        old: String,
+        new: String,
+    },
+    Completed,
+}
+
+/// A change to an existing task by a re-imported record with the same UID
+#[derive(Serialize)]
+struct UpdatedTask {
+    id: u32,
+    uid: String,
+    #[serde(flatten)]
+    change: Change,
+}
+
+/// Structured result of `import`
+#[derive(Serialize)]
+struct ImportResult<'a> {
+    command: &'static str,
+    dry_run: bool,
+    format: &'static str,
+    created_tags: &'a [String],
+    imported: &'a [Task],
+    updated: &'a [UpdatedTask],
+    unchanged: usize,
+    errors: &'a [RowError],
+}
+
+/// Parse `--map COLUMN=FIELD` values into a column mapping. FIELD is one of the CSV fields,
+/// in any case.
+fn parse_columns(columns: &[String]) -> Result<BTreeMap<String, String>, TaskError> {
+    columns
+        .iter()
+        .map(|mapping| {
+            let (column, field) = mapping.split_once('=').ok_or_else(|| {
+                TaskError::validation(format!(
+                    "Invalid column mapping \"{}\" (expected COLUMN=FIELD)",
+                    mapping
+                ))
+            })?;
+            let field = field.trim();
+            if !FIELDS.iter().any(|f| f.eq_ignore_ascii_case(field)) {
+                return Err(TaskError::validation(format!(
+                    "Unknown field \"{}\" in column mapping \"{}\" (expected one of {})",
+                    field,
+                    mapping,
+                    FIELDS.join(", ")
+                )));
+            }
+            Ok((column.trim().to_string(), field.to_string()))
+        })
+        .collect()
+}
+
+/// Bring `existing` in line with a re-imported record: take its deadline and, if it has
+/// one, its name, and complete the task if the record is completed. Tasks are never
+/// reopened, since many sources do not record completion.
+fn merge(existing: &mut Task, incoming: &Task) -> Vec<Change> {
+    let mut changes = Vec::new();
+    if existing.deadline != incoming.deadline {
+        changes.push(Change::Deadline {
+            old: existing.deadline,
+            new: incoming.deadline,
+        });
+        existing.deadline = incoming.deadline;
+    }
+    if !incoming.name.is_empty() && existing.name != incoming.name {
+        changes.push(Change::Name {
+            old: std::mem::replace(&mut existing.name, incoming.name.clone()),
+            new: incoming.name.clone(),
+        });
+    }
+    if incoming.completed && !existing.completed {
+        existing.complete(incoming.completed_at.unwrap_or_else(Local::now));
+        changes.push(Change::Completed);
+    }
+    changes
+}
+
+/// Import tasks from `file`. Every readable record becomes a new task with the next free ID
+/// after passing `Task::validate`, and missing tags are created. Records that fail are
+/// reported by row; the others are still imported.
+///
+/// A record with the UID of a task already in the active list updates that task (see
+/// `merge`) and is skipped if nothing changed, as is one matching an archived task, so a
+/// feed or an edited export can be imported repeatedly.
+/// With `dry_run` the tasks that would be imported are reported and nothing is saved.
+pub fn import_tasks(
+    format: ExchangeFormat,
+    file: PathBuf,
+    columns: Vec<String>,
+    tag_from: Option<IcsTagSource>,
+    settings: &Settings,
+    opts: &GlobalOptions,
+) -> Result<()> {
+    if !columns.is_empty() && format != ExchangeFormat::Csv {
+        return Err(TaskError::validation("--map only applies to --format csv").into());
+    }
+    if tag_from.is_some() && format != ExchangeFormat::Ics {
+        return Err(TaskError::validation("--tag-from only applies to --format ics").into());
+    }
+    let options = ImportOptions {
+        columns: parse_columns(&columns)?,
+        date_input_format: settings.date_input_format.as_deref(),
+        default_tag: settings.default_tag.as_deref(),
+        ics_tag_from: tag_from.unwrap_or_default(),
+    };
+    let content = fs::read_to_string(&file)
+        .map_err(|e| TaskError::storage(format!("Failed to read {}", file.display()), e))?;
+    let rows = formats::import(format, &content, &options)?;
+    let total = rows.len();
+
+    let _lock = storage::lock_storage()?;
+    let mut tasks = storage::load_tasks()?;
+    let mut tags = storage::load_tags()?;
+    let archive = storage::load_archive()?;
+
+    let mut imported = Vec::new();
+    let mut updated = Vec::new();
+    let mut unchanged = 0;
+    let mut created_tags = Vec::new();
+    let mut errors = Vec::new();
+    for row in rows {
+        if let Ok(incoming) = &row.task {
+            if let Some(uid) = &incoming.uid {
+                if let Some(existing) = tasks.iter_mut().find(|t| t.uid.as_ref() == Some(uid)) {
+                    let mut merged = existing.clone();
+                    let changes = merge(&mut merged, incoming);
+                    if let Err(e) = merged.validate() {
+                        errors.push(RowError {
+                            row: row.row,
+                            message: e.to_string(),
+                        });
+                    } else if changes.is_empty() {
+                        unchanged += 1;
+                    } else {
+                        *existing = merged;
+                        updated.extend(changes.into_iter().map(|change| UpdatedTask {
+                            id: existing.id,
+                            uid: uid.clone(),
+                            change,
+                        }));
+                    }
+                    continue;
+                }
+                if archive.iter().any(|a| a.task.uid.as_ref() == Some(uid)) {
+                    unchanged += 1;
+                    continue;
+                }
+            }
+        }
+
+        let task = row.task.and_then(|mut task| {
+            task.id = storage::get_next_task_id(&tasks);
+            if task.created_at.is_none() {
+                task.created_at = Some(Local::now());
+            }
+            task.validate().map_err(|e| e.to_string())?;
+            Ok(task)
+        });
+        match task {
+            Ok(task) => {
+                if !tag_exists(&tags, &task.tag) {
+                    tags.push(Tag::new(task.tag.clone()));
+                    created_tags.push(task.tag.clone());
+                }
+                tasks.push(task.clone());
+                imported.push(task);
+            }
+            Err(message) => errors.push(RowError {
+                row: row.row,
+                message,
+            }),
+        }
+    }
+
+    if (!imported.is_empty() || !updated.is_empty()) && !opts.dry_run {
+        storage::save_tasks(&tasks)?;
+        storage::save_tags(&tags)?;
+    }
+
+    if opts.output.is_structured() {
+        let result = ImportResult {
+            command: "import",
+            dry_run: opts.dry_run,
+            format: format.as_str(),
+            created_tags: &created_tags,
+            imported: &imported,
+            updated: &updated,
+            unchanged,
+            errors: &errors,
+        };
+        output::print_result(opts.output, &result);
+    } else {
+        let (verb, tag_verb, update_verb, complete_verb) = if opts.dry_run {
+            (
+                "Would import",
+                "Would create",
+                "Would update",
+                "Would complete",
+            )
+        } else {
+            ("Imported", "Created", "Updated", "Completed")
+        };
+        let show_date = |date: Option<NaiveDate>| match date {
+            Some(date) => date.format(&settings.date_output_format).to_string(),
+            None => "none".to_string(),
+        };
+        for tag in &created_tags {
+            println!("{} new tag: {}", tag_verb, tag);
+        }
+        let noun = if imported.len() == 1 { "task" } else { "tasks" };
+        println!(
+            "{} {} {} from {}",
+            verb,
+            imported.len(),
+            noun,
+            file.display()
+        );
+        for task in &updated {
+            match &task.change {
+                Change::Deadline { old, new } => println!(
+                    "{} deadline of task {:03}: {} -> {}",
+                    update_verb,
+                    task.id,
+                    show_date(*old),
+                    show_date(*new)
+                ),
+                Change::Name { old, new } => println!(
+                    "{} name of task {:03}: \"{}\" -> \"{}\"",
+                    update_verb, task.id, old, new
+                ),
+                Change::Completed => println!("{} task {:03}", complete_verb, task.id),
+            }
+        }
+        if unchanged > 0 {
+            let noun = if unchanged == 1 { "task" } else { "tasks" };
+            println!("Skipped {} unchanged {}", unchanged, noun);
+        }
+        for error in &errors {
+            eprintln!("Row {}: {}", error.row, error.message);
+        }
+    }
+
+    if !errors.is_empty() {
+        let message = format!("{} of {} rows could not be imported", errors.len(), total);
+        // Rows that were imported are saved, which a validation error would not tell
+        if imported.is_empty() && updated.is_empty() {
+            return Err(TaskError::validation(message).into());
+        }
+        return Err(TaskError::PartialSuccess(message).into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_never_reopens() {
+        let mut existing = Task::new(1, "Lab".to_string(), "Physics".to_string(), None);
+        let mut incoming = existing.clone();
+        incoming.name = "Lab report".to_string();
+        incoming.deadline = NaiveDate::from_ymd_opt(2026, 3, 5);
+        incoming.completed = true;
+
+        let changes = merge(&mut existing, &incoming);
+        assert_eq!(changes.len(), 3);
+        assert_eq!(changes[2], Change::Completed);
+        assert!(existing.completed && existing.completed_at.is_some());
+        assert_eq!(existing.name, "Lab report");
+
+        incoming.completed = false;
+        assert!(merge(&mut existing, &incoming).is_empty());
+        assert!(existing.completed);
+    }
+
+    #[test]
+    fn test_parse_columns() {
+        let columns =
+            parse_columns(&["Title=name".to_string(), " Due = deadline".to_string()]).unwrap();
+        assert_eq!(columns["Title"], "name");
+        assert_eq!(columns["Due"], "deadline");
+        assert!(parse_columns(&["Title".to_string()]).is_err());
+        let err = parse_columns(&["Course=tagg".to_string()]).unwrap_err();
+        assert!(err.to_string().starts_with("Unknown field \"tagg\""));
+        assert!(parse_columns(&["Course=TAG".to_string()]).is_ok());
+    }
+}
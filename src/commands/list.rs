@@ -1,30 +1,96 @@
+use crate::commands::GlobalOptions;
+use crate::config::{Settings, SortOrder, UrgencyWeights};
 use crate::models::Task;
+use crate::output;
 use crate::storage;
+use crate::template::{self, RenderContext, Template};
+use crate::theme::{Style, Theme};
+use crate::ui;
 use anyhow::Result;
+use chrono::{Local, NaiveDate};
+use comfy_table::{presets, Cell, CellAlignment, ColumnConstraint, ContentArrangement, Row, Table};
+use std::cmp::Ordering;
 use std::collections::BTreeMap;
 
-/// Apply ANSI strikethrough to a string for supported terminals
-fn strike(s: &str) -> String {
-    // ANSI strikethrough: ESC[9m ... ESC[0m
-    format!("\x1b[9m{}\x1b[0m", s)
+/// Columns taken by the id and separator after the indent: 3-digit id + " - "
+const ID_WIDTH: usize = 6;
+/// The name column never shrinks below this, even on very narrow terminals
+const MIN_NAME_WIDTH: usize = 10;
+/// Index of the name column in table output
+const NAME_COLUMN: usize = 2;
+/// Prefix marking completed tasks when strikethrough is unavailable
+const COMPLETED_MARKER: &str = "[x] ";
+
+/// Everything besides the task itself that decides how a row is rendered
+struct RowLayout<'a> {
+    /// Styles, date format, indent and padding
+    theme: &'a Theme,
+    /// Width of the name column; dates start `theme.padding` columns after it
+    name_width: usize,
+    /// Width of the widest formatted date
+    date_width: usize,
+    /// Terminal width, when known
+    term_width: Option<usize>,
+    /// Wrap names that do not fit instead of truncating them
+    wrap: bool,
+    /// Use ANSI styling
+    color: bool,
+    /// Reference date for overdue and due-today highlighting
+    today: NaiveDate,
 }
 
-/// Load tasks, group them by tag, sort by deadline (soonest first within each group),
-/// and print grouped output. Tag headers are non-indented; task rows are indented.
-///
-/// Output format per task: `{id:03} - {name}{padding}{date}` where date is `DD.MM.YYYY`.
-pub fn list_tasks() -> Result<()> {
-    let tasks = storage::load_tasks()?;
+impl RowLayout<'_> {
+    /// Columns before the name: indent + id + " - "
+    fn prefix_width(&self) -> usize {
+        self.theme.indent + ID_WIDTH
+    }
 
-    if tasks.is_empty() {
-        println!("No tasks found");
-        return Ok(());
+    /// Widest a name may be on a row with a date, given the terminal width
+    fn dated_name_limit(&self, term_width: usize) -> usize {
+        term_width
+            .saturating_sub(self.prefix_width() + self.theme.padding + self.date_width)
+            .max(MIN_NAME_WIDTH)
     }
+}
 
-    // Compute global maximum task name length so date columns align across all groups
-    let global_max_name_len = tasks.iter().map(|t| t.name.len()).max().unwrap_or(0);
+/// Name as shown in the name column. Without styling, completed tasks get a `[x]` marker
+/// since strikethrough is not available.
+fn display_name(task: &Task, color: bool) -> String {
+    if task.completed && !color {
+        format!("{}{}", COMPLETED_MARKER, task.name)
+    } else {
+        task.name.clone()
+    }
+}
+
+/// Format a task's deadline with the theme's date format, or an empty string without one
+fn format_deadline(task: &Task, theme: &Theme) -> String {
+    match task.deadline {
+        Some(d) => format!("{}", d.format(&theme.date_format)),
+        None => String::new(),
+    }
+}
+
+/// Style for a whole row: completed, overdue or due today, or None for other active tasks
+fn row_style<'a>(task: &Task, theme: &'a Theme, today: NaiveDate) -> Option<&'a Style> {
+    if task.completed {
+        return Some(&theme.completed);
+    }
+    match task.deadline {
+        Some(d) if d < today => Some(&theme.overdue),
+        Some(d) if d == today => Some(&theme.due_today),
+        _ => None,
+    }
+}
 
-    // Group tasks by tag using BTreeMap for stable, sorted tag order
+/// Group tasks by tag using BTreeMap for stable, sorted tag order, and sort each group
+/// by `sort`.
+pub(crate) fn group_tasks(
+    tasks: Vec<Task>,
+    sort: SortOrder,
+    urgency: &UrgencyWeights,
+    today: NaiveDate,
+) -> BTreeMap<String, Vec<Task>> {
     let mut groups: BTreeMap<String, Vec<Task>> = BTreeMap::new();
 
     for task in tasks.into_iter() {
@@ -36,44 +102,147 @@ pub fn list_tasks() -> Result<()> {
         groups.entry(tag).or_default().push(task);
     }
 
-    // For each group, sort tasks by deadline (soonest first). Tasks without deadline go last.
     for (_tag, tasks) in groups.iter_mut() {
-        tasks.sort_by(|a, b| match (&a.deadline, &b.deadline) {
-            (Some(ad), Some(bd)) => ad.cmp(bd),
-            (Some(_), None) => std::cmp::Ordering::Less,
-            (None, Some(_)) => std::cmp::Ordering::Greater,
-            (None, None) => a.id.cmp(&b.id),
-        });
+        tasks.sort_by(|a, b| compare_tasks(a, b, sort, urgency, today));
+    }
+
+    groups
+}
+
+/// Compare two tasks by `sort`. Sorting by deadline puts the soonest first and tasks without
+/// deadline last; it also breaks ties for the other orders.
+fn compare_tasks(
+    a: &Task,
+    b: &Task,
+    sort: SortOrder,
+    urgency: &UrgencyWeights,
+    today: NaiveDate,
+) -> Ordering {
+    let by_deadline = || match (&a.deadline, &b.deadline) {
+        (Some(ad), Some(bd)) => ad.cmp(bd).then(a.id.cmp(&b.id)),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => a.id.cmp(&b.id),
+    };
+    // Overdue first, finished last
+    let status_rank = |t: &Task| match template::status(t, today) {
+        "overdue" => 0,
+        "today" => 1,
+        "open" => 2,
+        _ => 3,
+    };
+
+    match sort {
+        SortOrder::Deadline => by_deadline(),
+        SortOrder::Urgency => urgency
+            .score(b, today)
+            .total_cmp(&urgency.score(a, today))
+            .then_with(by_deadline),
+        SortOrder::Id => a.id.cmp(&b.id),
+        SortOrder::Name => a
+            .name
+            .to_lowercase()
+            .cmp(&b.name.to_lowercase())
+            .then(a.id.cmp(&b.id)),
+        SortOrder::Tag => a.tag.cmp(&b.tag).then_with(by_deadline),
+        SortOrder::Status => status_rank(a).cmp(&status_rank(b)).then_with(by_deadline),
+    }
+}
+
+/// Load tasks, group them by tag, sort each group by the `sort` setting (deadline, soonest
+/// first, by default), and print grouped output. Tag headers are non-indented; task rows are indented.
+///
+/// Output format per task: `{id:03} - {name}{padding}{date}`. The date format comes from
+/// the `dates.output` setting, indent and padding from the theme (`DD.MM.YYYY`, 4 and 8
+/// by default).
+/// With a `template`, each task is printed as one templated line, without tag headers.
+/// With `table`, tasks are printed as a bordered table sorted across all tags.
+/// Structured output lists the tasks in the same order, without grouping.
+/// Long names are truncated to the terminal width, or wrapped when `wrap` is set.
+/// With color enabled, tag headers and completed, overdue and due-today tasks are styled
+/// by the theme.
+pub fn list_tasks(
+    opts: &GlobalOptions,
+    settings: &Settings,
+    template: Option<&Template>,
+    table: bool,
+    wrap: bool,
+) -> Result<()> {
+    let tasks = storage::load_tasks()?;
+    let theme = &settings.theme;
+    let today = Local::now().date_naive();
+    let group = |tasks| group_tasks(tasks, settings.sort, &settings.urgency, today);
+
+    if opts.output.is_structured() {
+        let ordered: Vec<Task> = group(tasks).into_values().flatten().collect();
+        output::print_records(opts.output, "list", "tasks", &ordered);
+        return Ok(());
+    }
+
+    if tasks.is_empty() {
+        println!("No tasks found");
+        return Ok(());
+    }
+
+    let color = opts.color.enabled();
+
+    if table {
+        print_table(tasks, settings, color, wrap, today);
+        return Ok(());
+    }
+
+    if let Some(template) = template {
+        let ctx = RenderContext {
+            date_format: &settings.date_output_format,
+            today,
+            urgency: &settings.urgency,
+        };
+        for task in group(tasks).into_values().flatten() {
+            let line = template.render(&task, &ctx);
+            match row_style(&task, theme, today) {
+                Some(style) if color => println!("{}", style.paint(&line)),
+                _ => println!("{}", line),
+            }
+        }
+        return Ok(());
+    }
+
+    // Compute global maximum task name width so date columns align across all groups
+    let global_max_name_width = tasks
+        .iter()
+        .map(|t| ui::display_width(&display_name(t, color)))
+        .max()
+        .unwrap_or(0);
+
+    let mut layout = RowLayout {
+        theme,
+        name_width: global_max_name_width,
+        date_width: tasks
+            .iter()
+            .map(|t| ui::display_width(&format_deadline(t, theme)))
+            .max()
+            .unwrap_or(0),
+        term_width: ui::terminal_width(),
+        wrap,
+        color,
+        today,
+    };
+    if let Some(w) = layout.term_width {
+        layout.name_width = layout.name_width.min(layout.dated_name_limit(w));
     }
 
+    let groups = group(tasks);
+
     // Print groups with properly aligned columns
     for (tag, tasks) in groups {
-        println!("{}", tag);
-        let max_name_len = global_max_name_len;
+        if color {
+            println!("{}", theme.header_style(&tag).paint(&tag));
+        } else {
+            println!("{}", tag);
+        }
         for task in tasks {
-            let date_str = match task.deadline {
-                Some(d) => format!("{}", d.format("%d.%m.%Y")),
-                None => String::new(),
-            };
-            // Format: 4-space indent + id (3 chars) + " - " + name + padding + date
-            let padding = " ".repeat((max_name_len.saturating_sub(task.name.len())) + 8);
-            if task.completed {
-                // Completed: id normal, name and date struck-through
-                if date_str.is_empty() {
-                    println!("    {:03} - {}", task.id, strike(&task.name));
-                } else {
-                    println!(
-                        "    {:03} - {}{}{}",
-                        task.id,
-                        strike(&task.name),
-                        padding,
-                        strike(&date_str)
-                    );
-                }
-            } else if date_str.is_empty() {
-                println!("    {:03} - {}", task.id, task.name);
-            } else {
-                println!("    {:03} - {}{}{}", task.id, task.name, padding, date_str);
+            for line in render_task(&task, &layout) {
+                println!("{}", line);
             }
         }
     }
@@ -81,6 +250,142 @@ pub fn list_tasks() -> Result<()> {
     Ok(())
 }
 
+/// Print tasks as a bordered table with ID, Tag, Name, Deadline, Days Left and Status
+/// columns, sorted by the `sort` setting across all tags. When the terminal width is known,
+/// only the name column shrinks to fit; long names are truncated, or wrapped with `wrap`.
+fn print_table(
+    mut tasks: Vec<Task>,
+    settings: &Settings,
+    color: bool,
+    wrap: bool,
+    today: NaiveDate,
+) {
+    let theme = &settings.theme;
+    tasks.sort_by(|a, b| {
+        compare_tasks(a, b, settings.sort, &settings.urgency, today).then_with(|| a.tag.cmp(&b.tag))
+    });
+
+    let mut table = Table::new();
+    table
+        .load_preset(presets::UTF8_FULL_CONDENSED)
+        .force_no_tty()
+        .set_truncation_indicator(&ui::ELLIPSIS.to_string());
+
+    let headers = ["ID", "Tag", "Name", "Deadline", "Days Left", "Status"];
+    table.set_header(headers.map(|h| {
+        if color {
+            theme.header.paint(h)
+        } else {
+            h.to_string()
+        }
+    }));
+
+    for task in &tasks {
+        let style = row_style(task, theme, today).filter(|_| color);
+        let paint = |s: String| match style {
+            Some(style) if !s.is_empty() => style.paint(&s),
+            _ => s,
+        };
+        let days_left = template::days_left(task, today)
+            .map(|d| d.to_string())
+            .unwrap_or_default();
+
+        let mut row = Row::from(vec![
+            Cell::new(format!("{:03}", task.id)),
+            Cell::new(&task.tag),
+            Cell::new(paint(task.name.clone())),
+            Cell::new(paint(format_deadline(task, theme))),
+            Cell::new(days_left).set_alignment(CellAlignment::Right),
+            Cell::new(template::status(task, today)),
+        ]);
+        if !wrap {
+            row.max_height(1);
+        }
+        table.add_row(row);
+    }
+
+    if let Some(width) = ui::terminal_width() {
+        table
+            .set_content_arrangement(ContentArrangement::Dynamic)
+            .set_width(width.min(u16::MAX as usize) as u16);
+        // Every column but the name keeps its content width, so the name shrinks first
+        for (i, column) in table.column_iter_mut().enumerate() {
+            if i != NAME_COLUMN {
+                column.set_constraint(ColumnConstraint::ContentWidth);
+            }
+        }
+    }
+
+    println!("{}", table);
+}
+
+/// Render one task as output lines.
+///
+/// Format: indent + id (3 chars) + " - " + name + padding + date. The date is placed
+/// `theme.padding` columns after the `name_width` column. When the terminal width is known,
+/// names that do not fit are truncated with an ellipsis, or wrapped onto indented
+/// continuation lines if `wrap` is set.
+fn render_task(task: &Task, layout: &RowLayout) -> Vec<String> {
+    let theme = layout.theme;
+    let name = display_name(task, layout.color);
+    let date_str = format_deadline(task, theme);
+
+    // Rows without a date may use the whole line for the name
+    let limit = layout.term_width.map(|w| {
+        if date_str.is_empty() {
+            w.saturating_sub(layout.prefix_width()).max(MIN_NAME_WIDTH)
+        } else {
+            layout.dated_name_limit(w)
+        }
+    });
+
+    let name_lines = match limit {
+        Some(limit) if ui::display_width(&name) > limit => {
+            if layout.wrap {
+                ui::wrap_to_width(&name, limit)
+            } else {
+                vec![ui::truncate_to_width(&name, limit)]
+            }
+        }
+        _ => vec![name],
+    };
+
+    // Completed: id normal, name and date in the completed style (strikethrough by default).
+    // Active: overdue or due-today style depending on the deadline.
+    let style = if layout.color {
+        row_style(task, theme, layout.today)
+    } else {
+        None
+    };
+    let paint = |s: &str| match style {
+        Some(style) => style.paint(s),
+        None => s.to_string(),
+    };
+
+    let indent = " ".repeat(theme.indent);
+    let mut lines = Vec::with_capacity(name_lines.len());
+    for (i, name) in name_lines.iter().enumerate() {
+        if i > 0 {
+            lines.push(format!(
+                "{}{}",
+                " ".repeat(layout.prefix_width()),
+                paint(name)
+            ));
+            continue;
+        }
+
+        let mut line = format!("{}{:03} - {}", indent, task.id, paint(name));
+        if !date_str.is_empty() {
+            let padding = " "
+                .repeat(layout.name_width.saturating_sub(ui::display_width(name)) + theme.padding);
+            line.push_str(&padding);
+            line.push_str(&paint(&date_str));
+        }
+        lines.push(line);
+    }
+    lines
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -92,6 +397,24 @@ mod tests {
         Task::new(id, name.to_string(), tag.to_string(), deadline)
     }
 
+    fn mk_layout(
+        theme: &Theme,
+        name_width: usize,
+        term_width: Option<usize>,
+        wrap: bool,
+        color: bool,
+    ) -> RowLayout<'_> {
+        RowLayout {
+            theme,
+            name_width,
+            date_width: 10,
+            term_width,
+            wrap,
+            color,
+            today: NaiveDate::from_ymd_opt(2026, 2, 17).unwrap(),
+        }
+    }
+
     #[test]
     fn test_grouping_and_sorting() {
         let mut tasks = vec![
@@ -125,6 +448,62 @@ mod tests {
         assert_eq!(t2[1].id, 3); // no deadline
     }
 
+    #[test]
+    fn test_group_tasks_sort_orders() {
+        let today = NaiveDate::from_ymd_opt(2026, 2, 17).unwrap();
+        let weights = UrgencyWeights::default();
+        let tasks = vec![
+            mk_task(1, "beta", "T", Some("20.03.2026")),
+            mk_task(2, "Alpha", "T", None),
+            mk_task(3, "gamma", "T", Some("16.02.2026")),
+            mk_task(4, "delta", "T", Some("18.02.2026")),
+        ];
+        let ids = |sort| -> Vec<u32> {
+            group_tasks(tasks.clone(), sort, &weights, today)["T"]
+                .iter()
+                .map(|t| t.id)
+                .collect()
+        };
+
+        assert_eq!(ids(SortOrder::Deadline), vec![3, 4, 1, 2]);
+        assert_eq!(ids(SortOrder::Id), vec![1, 2, 3, 4]);
+        assert_eq!(ids(SortOrder::Name), vec![2, 1, 4, 3]);
+        // Overdue, then due this week, then the rest by deadline
+        assert_eq!(ids(SortOrder::Urgency), vec![3, 4, 1, 2]);
+
+        let weights = UrgencyWeights {
+            tags: [("T".to_string(), 1.0)].into(),
+            overdue: 0.0,
+            ..UrgencyWeights::default()
+        };
+        let ids: Vec<u32> = group_tasks(tasks, SortOrder::Urgency, &weights, today)["T"]
+            .iter()
+            .map(|t| t.id)
+            .collect();
+        assert_eq!(ids, vec![4, 3, 1, 2]);
+    }
+
+    #[test]
+    fn test_compare_tasks_by_tag_and_status() {
+        let today = NaiveDate::from_ymd_opt(2026, 2, 17).unwrap();
+        let weights = UrgencyWeights::default();
+        let mut done = mk_task(1, "A", "Beta", Some("10.02.2026"));
+        done.completed = true;
+        let mut tasks = [
+            done,
+            mk_task(2, "B", "Alpha", Some("20.02.2026")),
+            mk_task(3, "C", "Beta", Some("16.02.2026")),
+            mk_task(4, "D", "Alpha", Some("17.02.2026")),
+        ];
+        let mut ids = |sort| -> Vec<u32> {
+            tasks.sort_by(|a, b| compare_tasks(a, b, sort, &weights, today));
+            tasks.iter().map(|t| t.id).collect()
+        };
+
+        assert_eq!(ids(SortOrder::Tag), vec![4, 2, 1, 3]);
+        assert_eq!(ids(SortOrder::Status), vec![3, 4, 2, 1]);
+    }
+
     #[test]
     fn test_formatting_date_no_deadline() {
         let t = mk_task(5, "Task5", "X", None);
@@ -138,7 +517,7 @@ mod tests {
     #[test]
     fn test_strike_formatting() {
         let text = "Hello";
-        let struck = strike(text);
+        let struck = Theme::default().completed.paint(text);
         assert!(struck.contains("\x1b[9m"));
         assert!(struck.contains("\x1b[0m"));
         assert!(struck.contains("Hello"));
@@ -147,7 +526,7 @@ mod tests {
     #[test]
     fn test_strike_contains_original_text() {
         let text = "Task Name";
-        let struck = strike(text);
+        let struck = Theme::default().completed.paint(text);
         assert!(struck.contains("Task Name"));
     }
 
@@ -188,4 +567,80 @@ mod tests {
         // Check that one is completed
         assert!(t1.iter().any(|t| t.completed));
     }
+
+    #[test]
+    fn test_render_task_aligns_by_display_width() {
+        let theme = Theme::default();
+        let wide = mk_task(1, "Лекция", "T", Some("17.02.2026"));
+        let ascii = mk_task(2, "Lecture", "T", Some("17.02.2026"));
+        let layout = mk_layout(&theme, 7, None, false, false);
+        let wide_line = &render_task(&wide, &layout)[0];
+        let ascii_line = &render_task(&ascii, &layout)[0];
+        assert_eq!(ui::display_width(wide_line), ui::display_width(ascii_line));
+        assert!(wide_line.ends_with("        17.02.2026"));
+    }
+
+    #[test]
+    fn test_render_task_truncates_to_terminal() {
+        let t = mk_task(
+            3,
+            "A very long task name that cannot fit",
+            "T",
+            Some("17.02.2026"),
+        );
+        let lines = {
+            let theme = Theme::default();
+            let mut layout = mk_layout(&theme, 0, Some(40), false, false);
+            layout.name_width = layout.dated_name_limit(40);
+            render_task(&t, &layout)
+        };
+        assert_eq!(lines.len(), 1);
+        assert!(lines[0].contains('…'));
+        assert!(ui::display_width(&lines[0]) <= 40);
+    }
+
+    #[test]
+    fn test_render_task_wraps_to_terminal() {
+        let theme = Theme::default();
+        let t = mk_task(4, "A very long task name that cannot fit", "T", None);
+        let lines = render_task(&t, &mk_layout(&theme, 40, Some(30), true, false));
+        assert_eq!(lines.len(), 2);
+        assert!(lines[1].starts_with("          "));
+        assert!(lines.iter().all(|l| ui::display_width(l) <= 30));
+    }
+
+    #[test]
+    fn test_render_completed_without_color_uses_marker() {
+        let theme = Theme::default();
+        let mut t = mk_task(5, "Finish report", "T", Some("10.02.2026"));
+        t.completed = true;
+        let line = &render_task(&t, &mk_layout(&theme, 17, None, false, false))[0];
+        assert!(line.starts_with("    005 - [x] Finish report"));
+        assert!(!line.contains('\x1b'));
+    }
+
+    #[test]
+    fn test_render_colors_overdue_and_due_today() {
+        let theme = Theme::default();
+        let overdue = mk_task(6, "Late", "T", Some("16.02.2026"));
+        let today = mk_task(7, "Now", "T", Some("17.02.2026"));
+        let later = mk_task(8, "Later", "T", Some("18.02.2026"));
+        let layout = mk_layout(&theme, 5, None, false, true);
+        assert!(render_task(&overdue, &layout)[0].contains(&theme.overdue.paint("Late")));
+        assert!(render_task(&today, &layout)[0].contains(&theme.due_today.paint("Now")));
+        assert!(!render_task(&later, &layout)[0].contains('\x1b'));
+    }
+
+    #[test]
+    fn test_render_uses_theme_layout() {
+        let theme = Theme {
+            indent: 2,
+            padding: 3,
+            date_format: "%Y-%m-%d".to_string(),
+            ..Theme::default()
+        };
+        let t = mk_task(9, "Essay", "T", Some("20.02.2026"));
+        let line = &render_task(&t, &mk_layout(&theme, 5, None, false, false))[0];
+        assert_eq!(line, "  009 - Essay   2026-02-20");
+    }
 }
@@ -0,0 +1,252 @@
+use crate::commands::GlobalOptions;
+use crate::config::Settings;
+use crate::models::Task;
+use crate::output;
+use crate::storage;
+use crate::ui;
+use anyhow::Result;
+use chrono::{Datelike, Duration, Local, NaiveDate};
+use clap::ValueEnum;
+use serde::Serialize;
+
+/// Character used to draw histogram bars
+const BAR: &str = "█";
+
+/// Period length of the `load` histogram
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum LoadPeriod {
+    /// One row per day, starting today
+    #[default]
+    Day,
+    /// One row per week (Monday to Sunday), starting with the current week
+    Week,
+}
+
+impl LoadPeriod {
+    /// Number of periods shown when no `--span` is given
+    fn default_span(self) -> u32 {
+        match self {
+            LoadPeriod::Day => 14,
+            LoadPeriod::Week => 8,
+        }
+    }
+}
+
+/// One row of the histogram
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct Period {
+    start: NaiveDate,
+    end: NaiveDate,
+    tasks: usize,
+    busy: bool,
+}
+
+/// Structured result of `load`
+#[derive(Serialize)]
+struct LoadReport {
+    command: &'static str,
+    by: &'static str,
+    overdue: usize,
+    periods: Vec<Period>,
+}
+
+/// Count active deadlines per period, starting today (or the Monday of this week).
+/// A period is busy once its count reaches `limit`.
+fn count_periods(
+    tasks: &[Task],
+    by: LoadPeriod,
+    span: u32,
+    limit: u32,
+    today: NaiveDate,
+) -> Vec<Period> {
+    let (first, length) = match by {
+        LoadPeriod::Day => (today, 1),
+        LoadPeriod::Week => (
+            today - Duration::days(today.weekday().num_days_from_monday() as i64),
+            7,
+        ),
+    };
+
+    (0..span as i64)
+        .map(|i| {
+            let start = first + Duration::days(i * length);
+            let end = start + Duration::days(length - 1);
+            let count = tasks
+                .iter()
+                .filter(|t| !t.completed)
+                .filter(|t| t.deadline.is_some_and(|d| d >= start && d <= end))
+                .count();
+            Period {
+                start,
+                end,
+                tasks: count,
+                busy: count >= limit as usize,
+            }
+        })
+        .collect()
+}
+
+/// Bar length for `count` when the largest count must fit into `max_width` columns
+fn bar_length(count: usize, max_count: usize, max_width: usize) -> usize {
+    if max_count <= max_width {
+        count
+    } else {
+        (count * max_width).div_ceil(max_count)
+    }
+}
+
+/// Show a histogram of upcoming deadlines per day or week, marking periods that reach the
+/// `[workload]` limits as busy. Overdue tasks are counted separately above the histogram.
+pub fn show_load(
+    by: LoadPeriod,
+    span: Option<u32>,
+    settings: &Settings,
+    opts: &GlobalOptions,
+) -> Result<()> {
+    let tasks = storage::load_tasks()?;
+    let today = Local::now().date_naive();
+    let limit = match by {
+        LoadPeriod::Day => settings.workload.day_limit,
+        LoadPeriod::Week => settings.workload.week_limit,
+    };
+    let periods = count_periods(&tasks, by, span.unwrap_or(by.default_span()), limit, today);
+    let overdue = tasks
+        .iter()
+        .filter(|t| !t.completed && t.deadline.is_some_and(|d| d < today))
+        .count();
+
+    if opts.output.is_structured() {
+        let report = LoadReport {
+            command: "load",
+            by: match by {
+                LoadPeriod::Day => "day",
+                LoadPeriod::Week => "week",
+            },
+            overdue,
+            periods,
+        };
+        output::print_result(opts.output, &report);
+        return Ok(());
+    }
+
+    let theme = &settings.theme;
+    let color = opts.color.enabled();
+    let date_format = &settings.date_output_format;
+    let label = |p: &Period| match by {
+        LoadPeriod::Day => p.start.format(&format!("%a {}", date_format)).to_string(),
+        LoadPeriod::Week => format!(
+            "Week {:02} {}",
+            p.start.iso_week().week(),
+            p.start.format(date_format)
+        ),
+    };
+
+    let title = match by {
+        LoadPeriod::Day => "Deadlines per day",
+        LoadPeriod::Week => "Deadlines per week",
+    };
+    if color {
+        println!("{}", theme.header.paint(title));
+    } else {
+        println!("{}", title);
+    }
+    if overdue > 0 {
+        let line = format!("{} overdue", overdue);
+        if color {
+            println!("{}", theme.overdue.paint(&line));
+        } else {
+            println!("{}", line);
+        }
+    }
+
+    let label_width = periods
+        .iter()
+        .map(|p| ui::display_width(&label(p)))
+        .max()
+        .unwrap_or(0);
+    let max_count = periods.iter().map(|p| p.tasks).max().unwrap_or(0);
+    // Room for the label, the padding, the count and the busy marker
+    let bar_width = ui::terminal_width()
+        .map(|w| w.saturating_sub(label_width + theme.padding + max_count.to_string().len() + 7))
+        .unwrap_or(usize::MAX)
+        .max(1);
+
+    for period in &periods {
+        let label = label(period);
+        let mut line = format!(
+            "{}{}",
+            label,
+            " ".repeat(label_width - ui::display_width(&label) + theme.padding)
+        );
+        if period.tasks > 0 {
+            line.push_str(&BAR.repeat(bar_length(period.tasks, max_count, bar_width).max(1)));
+            line.push(' ');
+        }
+        line.push_str(&period.tasks.to_string());
+        if period.busy {
+            line.push_str(" busy");
+        }
+
+        if color && period.busy {
+            println!("{}", theme.overdue.paint(&line));
+        } else {
+            println!("{}", line);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::testing::date;
+
+    fn mk_task(id: u32, day: u32) -> Task {
+        Task::new(id, format!("T{}", id), "Tag".to_string(), Some(date(day)))
+    }
+
+    #[test]
+    fn test_count_periods_by_day() {
+        let mut done = mk_task(4, 17);
+        done.completed = true;
+        let tasks = vec![
+            mk_task(1, 16),
+            mk_task(2, 17),
+            mk_task(3, 17),
+            done,
+            mk_task(5, 19),
+        ];
+
+        let periods = count_periods(&tasks, LoadPeriod::Day, 3, 2, date(17));
+        let counts: Vec<(NaiveDate, usize, bool)> =
+            periods.iter().map(|p| (p.start, p.tasks, p.busy)).collect();
+        assert_eq!(
+            counts,
+            vec![
+                (date(17), 2, true),
+                (date(18), 0, false),
+                (date(19), 1, false)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_count_periods_by_week() {
+        let tasks = vec![mk_task(1, 16), mk_task(2, 22), mk_task(3, 23)];
+
+        let periods = count_periods(&tasks, LoadPeriod::Week, 2, 2, date(17));
+        assert_eq!(periods[0].start, date(16));
+        assert_eq!(periods[0].end, date(22));
+        assert_eq!(periods[0].tasks, 2);
+        assert!(periods[0].busy);
+        assert_eq!(periods[1].tasks, 1);
+    }
+
+    #[test]
+    fn test_bar_length() {
+        assert_eq!(bar_length(3, 5, 40), 3);
+        assert_eq!(bar_length(50, 100, 40), 20);
+        assert_eq!(bar_length(1, 100, 40), 1);
+    }
+}
@@ -1,4 +1,42 @@
 pub mod add;
+pub mod agenda;
+pub mod archive;
+pub mod backup;
+pub mod burndown;
+pub mod calendar;
 pub mod clear;
+pub mod config;
+pub mod export;
+pub mod import;
 pub mod list;
+pub mod load;
 pub mod remove;
+pub mod report;
+pub mod stats;
+
+use crate::output::OutputFormat;
+use crate::ui::ColorChoice;
+
+/// Options set through global CLI flags, shared by every command
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GlobalOptions {
+    /// Print what would change without saving anything
+    pub dry_run: bool,
+    /// Skip interactive confirmation prompts
+    pub assume_yes: bool,
+    /// Format for command results and errors
+    pub output: OutputFormat,
+    /// When to style text output with colors and strikethrough
+    pub color: ColorChoice,
+}
+
+/// Fixtures shared by the command unit tests
+#[cfg(test)]
+pub(crate) mod testing {
+    use chrono::NaiveDate;
+
+    /// A day of February 2026, a month in which the 17th is a Tuesday
+    pub fn date(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 2, d).unwrap()
+    }
+}
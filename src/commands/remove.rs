@@ -1,62 +1,177 @@
+use crate::commands::GlobalOptions;
+use crate::error::TaskError;
+use crate::output;
 use crate::storage;
 use anyhow::Result;
+use chrono::Local;
+use serde::Serialize;
+use std::collections::BTreeSet;
+
+/// Structured result of `remove`
+#[derive(Serialize)]
+struct RemoveResult {
+    command: &'static str,
+    dry_run: bool,
+    marked: Vec<u32>,
+    not_found: Vec<String>,
+}
 
 /// Parse an ID string like "001" or "12" into u32. Accept leading zeros.
-fn parse_id(s: &str) -> Option<u32> {
+pub(crate) fn parse_id(s: &str) -> Option<u32> {
     s.trim().parse::<u32>().ok()
 }
 
-/// Mark one or more tasks as completed. Accepts vector of ID strings (e.g., ["001","02"]).
-/// Prints an error message if some IDs were not found, but still marks valid ones.
-pub fn remove_tasks(ids: Vec<String>) -> Result<()> {
-    if ids.is_empty() {
-        anyhow::bail!("No IDs provided");
+/// A task selector given on the command line
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum IdSelector {
+    /// A single ID, e.g. "7"
+    Single(u32),
+    /// An inclusive range of IDs, e.g. "3-7"
+    Range(u32, u32),
+    /// All IDs from a start ID onwards, e.g. "12-"
+    From(u32),
+}
+
+impl IdSelector {
+    /// Check whether a task ID falls under this selector
+    fn contains(&self, id: u32) -> bool {
+        match *self {
+            IdSelector::Single(single) => id == single,
+            IdSelector::Range(start, end) => (start..=end).contains(&id),
+            IdSelector::From(start) => id >= start,
+        }
     }
+}
 
-    let mut tasks = storage::load_tasks()?;
+/// Parse a selector token: a single ID ("7"), a closed range ("3-7") or an open range ("12-").
+/// Returns None for malformed tokens, including reversed ranges like "7-3".
+fn parse_selector(s: &str) -> Option<IdSelector> {
+    let s = s.trim();
+    match s.split_once('-') {
+        None => parse_id(s).map(IdSelector::Single),
+        Some((start, "")) => parse_id(start).map(IdSelector::From),
+        Some((start, end)) => {
+            let (start, end) = (parse_id(start)?, parse_id(end)?);
+            (start <= end).then_some(IdSelector::Range(start, end))
+        }
+    }
+}
 
-    // Parse IDs and keep mapping from original string to numeric id
-    let mut requested: Vec<(String, u32)> = Vec::new();
+/// Mark one or more tasks as completed. Accepts vector of ID strings (e.g., ["001","02"]),
+/// ranges (e.g., ["3-7","12-"]), and optionally every active task with a tag or past its deadline.
+/// Malformed ID tokens abort the command before anything is changed.
+/// Returns `TaskError::NotFound` if some IDs were not found, but still marks valid ones.
+/// With `dry_run` the tasks that would be marked are printed and nothing is saved.
+pub fn remove_tasks(
+    ids: Vec<String>,
+    tag: Option<String>,
+    overdue: bool,
+    opts: &GlobalOptions,
+) -> Result<()> {
+    let dry_run = opts.dry_run;
+    let text = !opts.output.is_structured();
+
+    if ids.is_empty() && tag.is_none() && !overdue {
+        return Err(TaskError::validation("No IDs provided").into());
+    }
+
+    // Parse every token up front so malformed input never leads to a partial update
+    let mut requested: Vec<(String, IdSelector)> = Vec::new();
+    let mut invalid: Vec<String> = Vec::new();
     for s in ids.iter() {
-        if let Some(id) = parse_id(s) {
-            requested.push((s.clone(), id));
+        match parse_selector(s) {
+            Some(selector) => requested.push((s.clone(), selector)),
+            None => invalid.push(s.clone()),
         }
     }
 
-    if requested.is_empty() {
-        anyhow::bail!("No valid numeric IDs provided");
+    if !invalid.is_empty() {
+        return Err(TaskError::validation(format!(
+            "Invalid IDs or ranges: {}",
+            invalid.join(", ")
+        ))
+        .into());
     }
 
-    // Build a map of existing task ids
-    let mut existing_ids: std::collections::HashMap<u32, usize> = std::collections::HashMap::new();
-    for (i, t) in tasks.iter().enumerate() {
-        existing_ids.insert(t.id, i);
-    }
+    let _lock = storage::lock_storage()?;
+    let mut tasks = storage::load_tasks()?;
+    let now = Local::now();
+    let today = now.date_naive();
 
+    // Collect selected task IDs in ascending order
+    let mut selected: BTreeSet<u32> = BTreeSet::new();
     let mut not_found: Vec<String> = Vec::new();
-    let mut marked_any = false;
-
-    for (orig, id) in requested.into_iter() {
-        if let Some(&idx) = existing_ids.get(&id) {
-            // Mark as completed
-            tasks[idx].completed = true;
-            marked_any = true;
-        } else {
+
+    for (orig, selector) in requested.into_iter() {
+        // Ranges only pick up active tasks; a single ID is marked whatever its state
+        let matches: Vec<u32> = tasks
+            .iter()
+            .filter(|t| selector.contains(t.id))
+            .filter(|t| matches!(selector, IdSelector::Single(_)) || !t.completed)
+            .map(|t| t.id)
+            .collect();
+
+        if matches.is_empty() {
             not_found.push(orig);
         }
+        selected.extend(matches);
+    }
+
+    if let Some(tag) = &tag {
+        let matches: Vec<u32> = tasks
+            .iter()
+            .filter(|t| &t.tag == tag && !t.completed)
+            .map(|t| t.id)
+            .collect();
+
+        if matches.is_empty() && text {
+            println!("No active tasks with tag: {}", tag);
+        }
+        selected.extend(matches);
+    }
+
+    if overdue {
+        let matches: Vec<u32> = tasks
+            .iter()
+            .filter(|t| !t.completed && t.deadline.is_some_and(|d| d < today))
+            .map(|t| t.id)
+            .collect();
+
+        if matches.is_empty() && text {
+            println!("No overdue tasks");
+        }
+        selected.extend(matches);
+    }
+
+    for task in tasks.iter_mut().filter(|t| selected.contains(&t.id)) {
+        if dry_run && text {
+            println!("Would mark task {:03} as completed: {}", task.id, task.name);
+        }
+        task.complete(now);
     }
 
     // Save updated tasks
-    if marked_any {
+    if !selected.is_empty() && !dry_run {
         storage::save_tasks(&tasks)?;
     }
 
+    if !text {
+        let result = RemoveResult {
+            command: "remove",
+            dry_run,
+            marked: selected.iter().copied().collect(),
+            not_found: not_found.clone(),
+        };
+        output::print_result(opts.output, &result);
+    }
+
     if !not_found.is_empty() {
-        // Print a single error message listing the invalid IDs
-        println!(
-            "One or more IDs could not be found: {}",
-            not_found.join(", ")
-        );
+        // Report the missing IDs as a single error; valid ones were still marked
+        return Err(TaskError::NotFound {
+            ids: not_found,
+            partial: !selected.is_empty(),
+        }
+        .into());
     }
 
     Ok(())
@@ -86,4 +201,29 @@ mod tests {
         assert_eq!(parse_id("  5  "), Some(5));
         assert_eq!(parse_id("\t10\t"), Some(10));
     }
+
+    #[test]
+    fn test_parse_selector_single_and_ranges() {
+        assert_eq!(parse_selector("010"), Some(IdSelector::Single(10)));
+        assert_eq!(parse_selector("3-7"), Some(IdSelector::Range(3, 7)));
+        assert_eq!(parse_selector("12-"), Some(IdSelector::From(12)));
+    }
+
+    #[test]
+    fn test_parse_selector_malformed() {
+        assert_eq!(parse_selector("abc"), None);
+        assert_eq!(parse_selector("7-3"), None);
+        assert_eq!(parse_selector("-5"), None);
+        assert_eq!(parse_selector("3-x"), None);
+        assert_eq!(parse_selector("1-2-3"), None);
+    }
+
+    #[test]
+    fn test_selector_contains() {
+        assert!(IdSelector::Range(3, 7).contains(3));
+        assert!(IdSelector::Range(3, 7).contains(7));
+        assert!(!IdSelector::Range(3, 7).contains(8));
+        assert!(IdSelector::From(12).contains(100));
+        assert!(!IdSelector::From(12).contains(11));
+    }
 }
@@ -0,0 +1,283 @@
+use crate::commands::burndown::escape_xml;
+use crate::commands::list::group_tasks;
+use crate::commands::stats::{
+    average_lead_days, completed_on, tag_stats, week_start, weekly_counts, DEFAULT_WEEKS,
+};
+use crate::commands::GlobalOptions;
+use crate::config::Settings;
+use crate::error::TaskError;
+use crate::models::Task;
+use crate::output;
+use crate::storage;
+use crate::template;
+use anyhow::Result;
+use chrono::{DateTime, Duration, Local, NaiveDate};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Styles of the report page, kept inline so the file has no external assets
+const STYLE: &str = "\
+body { font-family: system-ui, sans-serif; margin: 2rem auto; max-width: 60rem; color: #222; }
+h1 { margin-bottom: 0; }
+.generated { color: #666; margin-top: 0.2rem; }
+.summary { display: flex; flex-wrap: wrap; gap: 1rem; }
+.card { border: 1px solid #ddd; border-radius: 6px; padding: 0.6rem 1rem; min-width: 7rem; }
+.card .value { font-size: 1.6rem; font-weight: bold; }
+.card.overdue .value, tr.overdue td, .timeline .overdue { color: #b00020; }
+.card.today .value, tr.today td, .timeline .today { color: #b35c00; }
+tr.done td { color: #888; text-decoration: line-through; }
+table { border-collapse: collapse; width: 100%; margin-bottom: 1rem; }
+th, td { text-align: left; padding: 0.25rem 0.6rem; border-bottom: 1px solid #eee; }
+td.id { font-family: monospace; width: 4rem; }
+.timeline { list-style: none; padding-left: 0; border-left: 3px solid #ddd; }
+.timeline li { padding: 0.2rem 0 0.2rem 1rem; }
+.timeline .date { display: inline-block; min-width: 7rem; font-weight: bold; }
+.tag { color: #666; font-size: 0.85em; }
+.weeks { display: flex; align-items: flex-end; gap: 0.4rem; height: 8rem; }
+.week { flex: 1; display: flex; flex-direction: column; justify-content: flex-end; align-items: center; height: 100%; }
+.week .bar { width: 100%; background: #4a7bd0; }
+.week .label { font-size: 0.75em; color: #666; }
+.rate { display: inline-block; width: 8rem; height: 0.7rem; background: #eee; }
+.rate span { display: block; height: 100%; background: #3a9a5b; }
+@media print { body { margin: 0; max-width: none; } .card { break-inside: avoid; } }
+";
+
+/// Structured result of `report html`
+#[derive(Serialize)]
+struct ReportResult<'a> {
+    command: &'static str,
+    dry_run: bool,
+    format: &'static str,
+    file: &'a Path,
+}
+
+/// Render the report page for the active tasks, with completion statistics over the last
+/// weeks from both the active list and the archive
+fn render_html(
+    tasks: &[Task],
+    archived: &[&Task],
+    now: DateTime<Local>,
+    settings: &Settings,
+) -> String {
+    let today = now.date_naive();
+    let date_format = &settings.date_output_format;
+    let open: Vec<&Task> = tasks.iter().filter(|t| !t.completed).collect();
+    let count_status = |status: &str| {
+        open.iter()
+            .filter(|t| template::status(t, today) == status)
+            .count()
+    };
+
+    let since = week_start(today) - Duration::weeks(DEFAULT_WEEKS - 1);
+    let completed: Vec<&Task> = tasks
+        .iter()
+        .chain(archived.iter().copied())
+        .filter(|t| completed_on(t).is_some_and(|d| d >= since))
+        .collect();
+    let tags = tag_stats(&completed);
+    let (on_time, timed) = tags
+        .iter()
+        .fold((0, 0), |(o, n), t| (o + t.on_time, n + t.on_time + t.late));
+
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str(&format!(
+        "<title>Tasks {}</title>\n<style>\n{}</style>\n</head>\n<body>\n",
+        today.format(date_format),
+        STYLE
+    ));
+    html.push_str(&format!(
+        "<h1>Tasks</h1>\n<p class=\"generated\">Generated {}</p>\n",
+        now.format(&format!("{} %H:%M", date_format))
+    ));
+
+    // Summary cards
+    let rate = if timed > 0 {
+        format!("{:.0}%", on_time as f64 * 100.0 / timed as f64)
+    } else {
+        "-".to_string()
+    };
+    let lead = average_lead_days(&completed).map_or("-".to_string(), |d| format!("{:.1} d", d));
+    html.push_str("<section class=\"summary\">\n");
+    for (class, label, value) in [
+        ("open", "Open", open.len().to_string()),
+        ("overdue", "Overdue", count_status("overdue").to_string()),
+        ("today", "Due today", count_status("today").to_string()),
+        (
+            "completed",
+            &format!("Completed in {} weeks", DEFAULT_WEEKS),
+            completed.len().to_string(),
+        ),
+        ("rate", "On time", rate),
+        ("lead", "Average lead time", lead),
+    ] {
+        html.push_str(&format!(
+            "<div class=\"card {}\"><div class=\"value\">{}</div>{}</div>\n",
+            class, value, label
+        ));
+    }
+    html.push_str("</section>\n");
+
+    // Deadline timeline of open tasks
+    html.push_str("<h2>Timeline</h2>\n");
+    let mut by_day: BTreeMap<NaiveDate, Vec<&Task>> = BTreeMap::new();
+    for task in &open {
+        if let Some(deadline) = task.deadline {
+            by_day.entry(deadline).or_default().push(task);
+        }
+    }
+    if by_day.is_empty() {
+        html.push_str("<p>No open tasks with a deadline.</p>\n");
+    } else {
+        html.push_str("<ol class=\"timeline\">\n");
+        for (day, tasks) in &by_day {
+            let names: Vec<String> = tasks
+                .iter()
+                .map(|t| {
+                    format!(
+                        "{} <span class=\"tag\">{}</span>",
+                        escape_xml(&t.name),
+                        escape_xml(&t.tag)
+                    )
+                })
+                .collect();
+            html.push_str(&format!(
+                "<li class=\"{}\"><span class=\"date\">{}</span> {}</li>\n",
+                template::status(tasks[0], today),
+                day.format(date_format),
+                names.join(", ")
+            ));
+        }
+        html.push_str("</ol>\n");
+    }
+
+    // Tasks grouped by tag, as in `list`
+    html.push_str("<h2>Tasks by tag</h2>\n");
+    let groups = group_tasks(tasks.to_vec(), settings.sort, &settings.urgency, today);
+    if groups.is_empty() {
+        html.push_str("<p>No tasks found.</p>\n");
+    }
+    for (tag, tasks) in groups {
+        html.push_str(&format!(
+            "<h3>{}</h3>\n<table>\n<tr><th>ID</th><th>Task</th><th>Deadline</th><th>Status</th></tr>\n",
+            escape_xml(&tag)
+        ));
+        for task in &tasks {
+            let status = template::status(task, today);
+            html.push_str(&format!(
+                "<tr class=\"{}\"><td class=\"id\">{:03}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                status,
+                task.id,
+                escape_xml(&task.name),
+                task.deadline
+                    .map_or(String::new(), |d| d.format(date_format).to_string()),
+                status
+            ));
+        }
+        html.push_str("</table>\n");
+    }
+
+    // Completions per week and on-time rate per tag
+    html.push_str("<h2>Completion</h2>\n<div class=\"weeks\">\n");
+    let weeks = weekly_counts(&completed, since, today);
+    let max = weeks.iter().map(|w| w.completed).max().unwrap_or(0).max(1);
+    for week in &weeks {
+        html.push_str(&format!(
+            "<div class=\"week\" title=\"{}\"><span>{}</span><div class=\"bar\" style=\"height: {}%\"></div><span class=\"label\">{}</span></div>\n",
+            week.start.format(date_format),
+            week.completed,
+            week.completed * 100 / max,
+            week.start.format("%d.%m")
+        ));
+    }
+    html.push_str("</div>\n");
+    if !tags.is_empty() {
+        html.push_str("<table>\n<tr><th>Tag</th><th>Completed</th><th>On time</th></tr>\n");
+        for stats in &tags {
+            let cell = match stats.on_time_rate() {
+                Some(rate) => format!(
+                    "<span class=\"rate\"><span style=\"width: {:.0}%\"></span></span> {:.0}%",
+                    rate * 100.0,
+                    rate * 100.0
+                ),
+                None => "-".to_string(),
+            };
+            html.push_str(&format!(
+                "<tr><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                escape_xml(&stats.tag),
+                stats.completed,
+                cell
+            ));
+        }
+        html.push_str("</table>\n");
+    }
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+/// Write a self-contained HTML report to `file`, or stdout: summary counts, a timeline of
+/// open deadlines, the tasks grouped by tag with overdue ones highlighted, and completions
+/// over the last weeks. With `dry_run` nothing is written to the file.
+pub fn report_html(file: Option<PathBuf>, settings: &Settings, opts: &GlobalOptions) -> Result<()> {
+    let tasks = storage::load_tasks()?;
+    let archive = storage::load_archive()?;
+    let archived: Vec<&Task> = archive.iter().map(|e| &e.task).collect();
+    let html = render_html(&tasks, &archived, Local::now(), settings);
+
+    let Some(file) = file else {
+        print!("{}", html);
+        return Ok(());
+    };
+    if !opts.dry_run {
+        fs::write(&file, html)
+            .map_err(|e| TaskError::storage(format!("Failed to write {}", file.display()), e))?;
+    }
+
+    if opts.output.is_structured() {
+        let result = ReportResult {
+            command: "report",
+            dry_run: opts.dry_run,
+            format: "html",
+            file: &file,
+        };
+        output::print_result(opts.output, &result);
+        return Ok(());
+    }
+    let verb = if opts.dry_run { "Would write" } else { "Wrote" };
+    println!("{} report to {}", verb, file.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_render_html() {
+        let now = Local.with_ymd_and_hms(2026, 2, 17, 9, 0, 0).unwrap();
+        let day = |d| NaiveDate::from_ymd_opt(2026, 2, d);
+        let mut done = Task::new(3, "Essay".to_string(), "Writing".to_string(), day(16));
+        done.complete(Local.with_ymd_and_hms(2026, 2, 15, 12, 0, 0).unwrap());
+        let tasks = vec![
+            Task::new(1, "Lab <draft>".to_string(), "Physics".to_string(), day(16)),
+            Task::new(2, "Quiz".to_string(), "Physics".to_string(), day(17)),
+            done,
+        ];
+
+        let html = render_html(&tasks, &[], now, &Settings::default());
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(!html.contains("http"));
+        assert!(html.contains(
+            "<li class=\"overdue\"><span class=\"date\">16.02.2026</span> Lab &lt;draft&gt;"
+        ));
+        assert!(html.contains("<tr class=\"today\"><td class=\"id\">002</td><td>Quiz</td>"));
+        assert!(
+            html.contains("<div class=\"card overdue\"><div class=\"value\">1</div>Overdue</div>")
+        );
+        assert!(html.contains("<tr><td>Writing</td><td>1</td><td><span class=\"rate\"><span style=\"width: 100%\"></span></span> 100%</td></tr>"));
+    }
+}
@@ -0,0 +1,395 @@
+use crate::commands::add::parse_date;
+use crate::commands::GlobalOptions;
+use crate::config::Settings;
+use crate::error::TaskError;
+use crate::models::Task;
+use crate::output;
+use crate::storage;
+use crate::ui;
+use anyhow::Result;
+use chrono::{Datelike, Duration, Local, NaiveDate};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Sparkline levels, lowest first
+const SPARKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+/// Weeks shown when no `--since` is given, including the current one
+pub(crate) const DEFAULT_WEEKS: i64 = 8;
+/// Width of the on-time rate bars
+const RATE_BAR_WIDTH: usize = 10;
+
+/// Completions in one week (Monday to Sunday)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(crate) struct WeekCount {
+    pub start: NaiveDate,
+    pub completed: usize,
+}
+
+/// On-time record of one tag. Only completed tasks with a deadline are on time or late.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub(crate) struct TagStats {
+    pub tag: String,
+    pub completed: usize,
+    pub on_time: usize,
+    pub late: usize,
+}
+
+impl TagStats {
+    /// Share of tasks with a deadline completed on or before it, None without any
+    pub fn on_time_rate(&self) -> Option<f64> {
+        let total = self.on_time + self.late;
+        (total > 0).then(|| self.on_time as f64 / total as f64)
+    }
+}
+
+/// Structured result of `stats`
+#[derive(Serialize)]
+struct StatsReport {
+    command: &'static str,
+    since: NaiveDate,
+    until: NaiveDate,
+    completed: usize,
+    weeks: Vec<WeekCount>,
+    tags: Vec<TagRecord>,
+    average_lead_days: Option<f64>,
+    overdue: usize,
+    current_streak: usize,
+    longest_streak: usize,
+}
+
+/// A tag in the structured result, with its rate
+#[derive(Serialize)]
+struct TagRecord {
+    #[serde(flatten)]
+    stats: TagStats,
+    on_time_rate: Option<f64>,
+}
+
+/// Monday of the week containing `date`
+pub(crate) fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+/// Day a task was completed, if recorded
+pub(crate) fn completed_on(task: &Task) -> Option<NaiveDate> {
+    task.completed_at
+        .filter(|_| task.completed)
+        .map(|at| at.date_naive())
+}
+
+/// Completions per week for every week touching `since..=until`; `tasks` are already
+/// limited to that range
+pub(crate) fn weekly_counts(tasks: &[&Task], since: NaiveDate, until: NaiveDate) -> Vec<WeekCount> {
+    let mut weeks = Vec::new();
+    let mut start = week_start(since);
+    while start <= until {
+        let end = start + Duration::days(6);
+        let completed = tasks
+            .iter()
+            .filter_map(|t| completed_on(t))
+            .filter(|&d| d >= start && d <= end)
+            .count();
+        weeks.push(WeekCount { start, completed });
+        start += Duration::days(7);
+    }
+    weeks
+}
+
+/// On-time and late completions per tag, by tag name
+pub(crate) fn tag_stats(tasks: &[&Task]) -> Vec<TagStats> {
+    let mut by_tag: BTreeMap<&str, TagStats> = BTreeMap::new();
+    for task in tasks {
+        let Some(done) = completed_on(task) else {
+            continue;
+        };
+        let stats = by_tag.entry(&task.tag).or_insert_with(|| TagStats {
+            tag: task.tag.clone(),
+            ..TagStats::default()
+        });
+        stats.completed += 1;
+        match task.deadline {
+            Some(deadline) if done <= deadline => stats.on_time += 1,
+            Some(_) => stats.late += 1,
+            None => {}
+        }
+    }
+    by_tag.into_values().collect()
+}
+
+/// Average days from creation to completion, over tasks with both timestamps
+pub(crate) fn average_lead_days(tasks: &[&Task]) -> Option<f64> {
+    let leads: Vec<f64> = tasks
+        .iter()
+        .filter(|t| t.completed)
+        .filter_map(|t| Some((t.completed_at? - t.created_at?).num_seconds() as f64 / 86_400.0))
+        .collect();
+    (!leads.is_empty()).then(|| leads.iter().sum::<f64>() / leads.len() as f64)
+}
+
+/// Current and longest run of consecutive days with at least one completion.
+/// The current streak still counts when nothing has been completed yet today.
+fn streaks(days: &BTreeSet<NaiveDate>, today: NaiveDate) -> (usize, usize) {
+    let mut longest = 0;
+    let mut run = 0;
+    let mut previous: Option<NaiveDate> = None;
+    for &day in days {
+        run = match previous {
+            Some(p) if day - p == Duration::days(1) => run + 1,
+            _ => 1,
+        };
+        longest = longest.max(run);
+        previous = Some(day);
+    }
+
+    let mut current = 0;
+    let mut day = if days.contains(&today) {
+        today
+    } else {
+        today - Duration::days(1)
+    };
+    while days.contains(&day) {
+        current += 1;
+        day -= Duration::days(1);
+    }
+    (current, longest)
+}
+
+/// One sparkline character per count, scaled to the largest count
+fn sparkline(counts: &[usize]) -> String {
+    let max = counts.iter().copied().max().unwrap_or(0).max(1);
+    counts
+        .iter()
+        .map(|&c| SPARKS[c * (SPARKS.len() - 1) / max])
+        .collect()
+}
+
+/// A bar of `width` cells, filled by `rate`
+fn rate_bar(rate: f64, width: usize) -> String {
+    let filled = (rate * width as f64).round() as usize;
+    format!("{}{}", "█".repeat(filled), "░".repeat(width - filled))
+}
+
+/// Show completion statistics between `since` and `until` (default: the last eight weeks):
+/// completions per week, on-time rate per tag, average lead time, plus the current overdue
+/// count and completion streaks. Archived tasks count too.
+pub fn show_stats(
+    since: Option<String>,
+    until: Option<String>,
+    settings: &Settings,
+    opts: &GlobalOptions,
+) -> Result<()> {
+    let today = Local::now().date_naive();
+    let input_format = settings.date_input_format.as_deref();
+    let until = match until {
+        Some(s) => parse_date(&s, input_format)?,
+        None => today,
+    };
+    let since = match since {
+        Some(s) => parse_date(&s, input_format)?,
+        None => week_start(until) - Duration::weeks(DEFAULT_WEEKS - 1),
+    };
+    if since > until {
+        return Err(TaskError::validation("--since must not be after --until").into());
+    }
+
+    let tasks = storage::load_tasks()?;
+    let archive = storage::load_archive()?;
+    let all: Vec<&Task> = tasks
+        .iter()
+        .chain(archive.iter().map(|e| &e.task))
+        .collect();
+    let in_range: Vec<&Task> = all
+        .iter()
+        .copied()
+        .filter(|t| completed_on(t).is_some_and(|d| d >= since && d <= until))
+        .collect();
+
+    let weeks = weekly_counts(&in_range, since, until);
+    let tags = tag_stats(&in_range);
+    let lead = average_lead_days(&in_range);
+    let overdue = tasks
+        .iter()
+        .filter(|t| !t.completed && t.deadline.is_some_and(|d| d < today))
+        .count();
+    let days: BTreeSet<NaiveDate> = all.iter().filter_map(|t| completed_on(t)).collect();
+    let (current_streak, longest_streak) = streaks(&days, today);
+
+    if opts.output.is_structured() {
+        let report = StatsReport {
+            command: "stats",
+            since,
+            until,
+            completed: in_range.len(),
+            weeks,
+            tags: tags
+                .into_iter()
+                .map(|stats| TagRecord {
+                    on_time_rate: stats.on_time_rate(),
+                    stats,
+                })
+                .collect(),
+            average_lead_days: lead,
+            overdue,
+            current_streak,
+            longest_streak,
+        };
+        output::print_result(opts.output, &report);
+        return Ok(());
+    }
+
+    let theme = &settings.theme;
+    let color = opts.color.enabled();
+    let date_format = &settings.date_output_format;
+    let heading = |s: &str| {
+        if color {
+            println!("{}", theme.header.paint(s));
+        } else {
+            println!("{}", s);
+        }
+    };
+    let indent = " ".repeat(theme.indent);
+
+    heading(&format!(
+        "Statistics {} - {}",
+        since.format(date_format),
+        until.format(date_format)
+    ));
+    let counts: Vec<usize> = weeks.iter().map(|w| w.completed).collect();
+    println!(
+        "{}Completed per week  {}  {} total",
+        indent,
+        sparkline(&counts),
+        in_range.len()
+    );
+    println!(
+        "{}Average lead time   {}",
+        indent,
+        lead.map_or("-".to_string(), |d| format!("{:.1} days", d))
+    );
+    let overdue_line = format!("{}Overdue now         {}", indent, overdue);
+    if color && overdue > 0 {
+        println!("{}", theme.overdue.paint(&overdue_line));
+    } else {
+        println!("{}", overdue_line);
+    }
+    println!(
+        "{}Streak              {} {} (longest {})",
+        indent,
+        current_streak,
+        if current_streak == 1 { "day" } else { "days" },
+        longest_streak
+    );
+
+    let rated: Vec<(&TagStats, f64)> = tags
+        .iter()
+        .filter_map(|t| Some((t, t.on_time_rate()?)))
+        .collect();
+    if !rated.is_empty() {
+        println!();
+        heading("On time by tag");
+        let tag_width = rated
+            .iter()
+            .map(|(t, _)| ui::display_width(&t.tag))
+            .max()
+            .unwrap_or(0);
+        for (stats, rate) in rated {
+            let padding = " ".repeat(tag_width - ui::display_width(&stats.tag));
+            println!(
+                "{}{}{}  {} {:>3.0}% ({} of {})",
+                indent,
+                stats.tag,
+                padding,
+                rate_bar(rate, RATE_BAR_WIDTH),
+                rate * 100.0,
+                stats.on_time,
+                stats.on_time + stats.late
+            );
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::testing::date;
+    use chrono::TimeZone;
+
+    fn done(id: u32, tag: &str, deadline: Option<u32>, created: u32, completed: u32) -> Task {
+        let at = |d: u32| Local.from_local_datetime(&date(d).and_hms_opt(12, 0, 0).unwrap());
+        let mut task = Task::new(id, format!("T{}", id), tag.to_string(), deadline.map(date));
+        task.created_at = at(created).single();
+        task.complete(at(completed).single().unwrap());
+        task
+    }
+
+    #[test]
+    fn test_weekly_counts() {
+        let tasks = [
+            done(1, "A", None, 1, 5),
+            done(2, "A", None, 1, 9),
+            done(3, "A", None, 1, 10),
+        ];
+        let refs: Vec<&Task> = tasks.iter().collect();
+
+        let weeks = weekly_counts(&refs, date(4), date(17));
+        assert_eq!(
+            weeks,
+            vec![
+                WeekCount {
+                    start: date(2),
+                    completed: 1
+                },
+                WeekCount {
+                    start: date(9),
+                    completed: 2
+                },
+                WeekCount {
+                    start: date(16),
+                    completed: 0
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tag_stats_and_lead_time() {
+        let tasks = [
+            done(1, "Lab", Some(10), 1, 10),
+            done(2, "Lab", Some(10), 1, 12),
+            done(3, "Lab", None, 5, 6),
+            done(4, "Essay", Some(20), 10, 12),
+        ];
+        let refs: Vec<&Task> = tasks.iter().collect();
+
+        let stats = tag_stats(&refs);
+        assert_eq!(stats[0].tag, "Essay");
+        assert_eq!(stats[0].on_time_rate(), Some(1.0));
+        assert_eq!(
+            (stats[1].completed, stats[1].on_time, stats[1].late),
+            (3, 1, 1)
+        );
+        assert_eq!(stats[1].on_time_rate(), Some(0.5));
+
+        // (9 + 11 + 1 + 2) / 4 days
+        assert_eq!(average_lead_days(&refs), Some(5.75));
+        assert_eq!(average_lead_days(&[]), None);
+    }
+
+    #[test]
+    fn test_streaks() {
+        let days: BTreeSet<NaiveDate> = [1, 2, 3, 4, 10, 15, 16].into_iter().map(date).collect();
+        assert_eq!(streaks(&days, date(17)), (2, 4));
+        assert_eq!(streaks(&days, date(16)), (2, 4));
+        assert_eq!(streaks(&days, date(18)), (0, 4));
+        assert_eq!(streaks(&BTreeSet::new(), date(18)), (0, 0));
+    }
+
+    #[test]
+    fn test_charts() {
+        assert_eq!(sparkline(&[0, 1, 2, 4]), "▁▂▄█");
+        assert_eq!(sparkline(&[0, 0]), "▁▁");
+        assert_eq!(rate_bar(0.8, 10), "████████░░");
+    }
+}
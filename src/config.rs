@@ -0,0 +1,881 @@
+use crate::error::TaskError;
+use crate::models::Task;
+use crate::template::Template;
+use crate::theme::{self, Theme};
+use crate::ui::ColorChoice;
+use anyhow::Result;
+use chrono::NaiveDate;
+use clap::ValueEnum;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Prefix of the environment variables that override config keys,
+/// e.g. `SILVER_TASKS_DATES_OUTPUT` for `dates.output`
+const ENV_PREFIX: &str = "SILVER_TASKS_";
+
+/// Deadline format used when none is configured
+const DEFAULT_DATE_FORMAT: &str = "%d.%m.%Y";
+
+/// Settings read from `$XDG_CONFIG_HOME/silver-tasks/config.toml`.
+/// Every setting is optional; a missing file means built-in defaults.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Tag used by `add` when no tag is given
+    pub default_tag: Option<String>,
+    /// Order of tasks in `list`: deadline, urgency, id, name, tag or status
+    pub sort: Option<String>,
+    /// When to use colors: auto, always or never
+    pub color: Option<String>,
+    /// Directory holding the task files instead of the home directory
+    pub data_dir: Option<String>,
+    /// Named format or template used by `list` when no `--format` is given
+    pub list_format: Option<String>,
+    /// Named list templates, e.g. `compact = "{id} {name}"`
+    pub formats: BTreeMap<String, String>,
+    /// Deadline input and output formats
+    pub dates: DatesConfig,
+    /// Weights used to compute task urgency
+    pub urgency: UrgencyWeights,
+    /// Thresholds for deadline clustering warnings and `load`
+    pub workload: WorkloadLimits,
+    /// How many backups of the tasks file to keep
+    pub backup: BackupRetention,
+    /// Output styles and layout for list
+    pub theme: ThemeConfig,
+}
+
+/// The `[dates]` section
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DatesConfig {
+    /// chrono format tried first when parsing deadlines, before the built-in formats
+    pub input: Option<String>,
+    /// chrono format for printing deadlines, e.g. "%d.%m.%Y"
+    pub output: Option<String>,
+}
+
+/// The `[urgency]` section: how much each property of an active task adds to its urgency
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct UrgencyWeights {
+    /// Deadline has passed
+    pub overdue: f64,
+    /// Due today
+    pub due_today: f64,
+    /// Due within the next seven days
+    pub due_week: f64,
+    /// Extra weight per tag, e.g. `Calculus = 2.0`
+    pub tags: BTreeMap<String, f64>,
+}
+
+impl Default for UrgencyWeights {
+    fn default() -> Self {
+        Self {
+            overdue: 12.0,
+            due_today: 8.0,
+            due_week: 4.0,
+            tags: BTreeMap::new(),
+        }
+    }
+}
+
+impl UrgencyWeights {
+    /// Urgency of a task on `today`. Completed tasks are never urgent.
+    pub fn score(&self, task: &Task, today: NaiveDate) -> f64 {
+        if task.completed {
+            return 0.0;
+        }
+
+        let deadline = match task.deadline {
+            Some(d) if d < today => self.overdue,
+            Some(d) if d == today => self.due_today,
+            Some(d) if (d - today).num_days() <= 7 => self.due_week,
+            _ => 0.0,
+        };
+        deadline + self.tags.get(&task.tag).copied().unwrap_or(0.0)
+    }
+}
+
+/// The `[workload]` section: when deadlines count as piling up
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WorkloadLimits {
+    /// Days before and after a deadline that count as the same crunch
+    pub window_days: u32,
+    /// `add` warns when at least this many other active tasks are due within the window
+    pub warn_at: u32,
+    /// `load` marks days with at least this many deadlines as busy
+    pub day_limit: u32,
+    /// `load --by week` marks weeks with at least this many deadlines as busy
+    pub week_limit: u32,
+}
+
+impl Default for WorkloadLimits {
+    fn default() -> Self {
+        Self {
+            window_days: 2,
+            warn_at: 2,
+            day_limit: 3,
+            week_limit: 6,
+        }
+    }
+}
+
+impl WorkloadLimits {
+    /// Active tasks other than `task_id` due within `window_days` of `date`
+    pub fn nearby<'a>(&self, tasks: &'a [Task], task_id: u32, date: NaiveDate) -> Vec<&'a Task> {
+        tasks
+            .iter()
+            .filter(|t| !t.completed && t.id != task_id)
+            .filter(|t| {
+                t.deadline
+                    .is_some_and(|d| (d - date).num_days().abs() <= self.window_days as i64)
+            })
+            .collect()
+    }
+}
+
+/// The `[backup]` section: which backups of the tasks file are kept. Setting both to 0
+/// turns backups off.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BackupRetention {
+    /// Number of most recent backups to keep
+    pub keep: u32,
+    /// Also keep the newest backup of each of this many days, today included
+    pub daily_days: u32,
+}
+
+impl Default for BackupRetention {
+    fn default() -> Self {
+        Self {
+            keep: 10,
+            daily_days: 7,
+        }
+    }
+}
+
+/// The `[theme]` section: a built-in base theme plus individual overrides
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ThemeConfig {
+    /// Base theme: "dark" (default) or "light"
+    pub name: Option<String>,
+    /// Deprecated alias of `dates.output`, which wins when both are set
+    pub date_format: Option<String>,
+    /// Spaces before each task row
+    pub indent: Option<usize>,
+    /// Minimum gap between the longest name and the date column
+    pub padding: Option<usize>,
+    /// Per-status styles, e.g. `overdue = "bold red"`
+    pub styles: StyleConfig,
+    /// Per-tag header styles, e.g. `Calculus = "blue"`
+    pub tags: BTreeMap<String, String>,
+}
+
+/// The `[theme.styles]` section
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StyleConfig {
+    pub header: Option<String>,
+    pub completed: Option<String>,
+    pub overdue: Option<String>,
+    pub due_today: Option<String>,
+}
+
+/// Order of tasks within each tag group in `list`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum SortOrder {
+    /// Soonest deadline first, tasks without deadline last
+    #[default]
+    Deadline,
+    /// Most urgent first, see the `[urgency]` weights
+    Urgency,
+    /// Ascending task ID
+    Id,
+    /// Task name, case-insensitive
+    Name,
+    /// Tag, then deadline
+    Tag,
+    /// Overdue, due today, open, then completed
+    Status,
+}
+
+/// Kind of value a config key holds, used to type values from `config set` and env vars
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ValueKind {
+    Text,
+    Number,
+    Count,
+}
+
+/// Every fixed config key, in `config list` order
+const KEYS: &[(&str, ValueKind)] = &[
+    ("default_tag", ValueKind::Text),
+    ("sort", ValueKind::Text),
+    ("color", ValueKind::Text),
+    ("data_dir", ValueKind::Text),
+    ("list_format", ValueKind::Text),
+    ("dates.input", ValueKind::Text),
+    ("dates.output", ValueKind::Text),
+    ("urgency.overdue", ValueKind::Number),
+    ("urgency.due_today", ValueKind::Number),
+    ("urgency.due_week", ValueKind::Number),
+    ("workload.window_days", ValueKind::Count),
+    ("workload.warn_at", ValueKind::Count),
+    ("workload.day_limit", ValueKind::Count),
+    ("workload.week_limit", ValueKind::Count),
+    ("backup.keep", ValueKind::Count),
+    ("backup.daily_days", ValueKind::Count),
+    ("theme.name", ValueKind::Text),
+    ("theme.indent", ValueKind::Count),
+    ("theme.padding", ValueKind::Count),
+    ("theme.styles.header", ValueKind::Text),
+    ("theme.styles.completed", ValueKind::Text),
+    ("theme.styles.overdue", ValueKind::Text),
+    ("theme.styles.due_today", ValueKind::Text),
+];
+
+/// Keys whose last part is a tag or format name, e.g. `theme.tags.Calculus`
+const NAMED_KEYS: &[(&str, ValueKind)] = &[
+    ("formats.", ValueKind::Text),
+    ("theme.tags.", ValueKind::Text),
+    ("urgency.tags.", ValueKind::Number),
+];
+
+/// Split a config key into its table path, checking it is known.
+/// Tag names may contain dots, so only the fixed prefix of a named key is split.
+fn key_path(key: &str) -> Result<(Vec<&str>, ValueKind), TaskError> {
+    if let Some(&(_, kind)) = KEYS.iter().find(|(k, _)| *k == key) {
+        return Ok((key.split('.').collect(), kind));
+    }
+    for &(prefix, kind) in NAMED_KEYS {
+        if let Some(tag) = key.strip_prefix(prefix).filter(|t| !t.is_empty()) {
+            let mut path: Vec<&str> = prefix.trim_end_matches('.').split('.').collect();
+            path.push(tag);
+            return Ok((path, kind));
+        }
+    }
+    Err(TaskError::validation(format!(
+        "Unknown config key \"{}\"",
+        key
+    )))
+}
+
+/// Environment variable overriding a fixed key
+fn env_var_name(key: &str) -> String {
+    format!("{}{}", ENV_PREFIX, key.replace('.', "_").to_uppercase())
+}
+
+/// Convert a raw string into a TOML value of the key's kind
+fn typed_value(key: &str, raw: &str, kind: ValueKind) -> Result<toml::Value, TaskError> {
+    let invalid = || TaskError::validation(format!("Invalid value \"{}\" for {}", raw, key));
+    Ok(match kind {
+        ValueKind::Text => toml::Value::String(raw.to_string()),
+        ValueKind::Number => toml::Value::Float(raw.trim().parse().map_err(|_| invalid())?),
+        ValueKind::Count => {
+            let n: u32 = raw.trim().parse().map_err(|_| invalid())?;
+            toml::Value::Integer(n.into())
+        }
+    })
+}
+
+/// Look up a value by table path
+fn lookup<'a>(table: &'a toml::Table, path: &[&str]) -> Option<&'a toml::Value> {
+    let (last, parents) = path.split_last()?;
+    let mut table = table;
+    for part in parents {
+        table = table.get(*part)?.as_table()?;
+    }
+    table.get(*last)
+}
+
+/// Get the path to the config file
+pub fn config_file_path() -> Result<PathBuf> {
+    let dir = dirs::config_dir().ok_or_else(|| TaskError::Storage {
+        message: "Could not determine config directory".to_string(),
+        source: None,
+    })?;
+    Ok(dir.join("silver-tasks").join("config.toml"))
+}
+
+/// Read the config file as a TOML table; a missing file is an empty table
+fn read_table(path: &Path) -> Result<toml::Table> {
+    if !path.exists() {
+        return Ok(toml::Table::new());
+    }
+    let content = fs::read_to_string(path)
+        .map_err(|e| TaskError::storage("Failed to read config file", e))?;
+    parse_table(&content)
+}
+
+/// Parse config file contents into a TOML table
+fn parse_table(content: &str) -> Result<toml::Table> {
+    content
+        .parse::<toml::Table>()
+        .map_err(|e| TaskError::validation(format!("Invalid config file: {}", e.message())).into())
+}
+
+/// Deserialize a TOML table into a config
+fn from_table(table: toml::Table) -> Result<Config> {
+    table.try_into().map_err(|e: toml::de::Error| {
+        TaskError::validation(format!("Invalid config file: {}", e.message())).into()
+    })
+}
+
+/// Overlay `SILVER_TASKS_*` environment variables onto the config file values
+fn apply_env(table: &mut toml::Table) -> Result<()> {
+    for &(key, kind) in KEYS {
+        let Ok(raw) = std::env::var(env_var_name(key)) else {
+            continue;
+        };
+        let value = typed_value(&env_var_name(key), &raw, kind)?;
+
+        let path: Vec<&str> = key.split('.').collect();
+        let (last, parents) = path.split_last().expect("config keys are never empty");
+        let mut target = &mut *table;
+        for part in parents {
+            target = target
+                .entry(part.to_string())
+                .or_insert_with(|| toml::Value::Table(toml::Table::new()))
+                .as_table_mut()
+                .ok_or_else(|| {
+                    TaskError::validation(format!("Invalid config file: {} is not a table", part))
+                })?;
+        }
+        target.insert(last.to_string(), value);
+    }
+    Ok(())
+}
+
+/// Where the effective value of a key comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Source {
+    Default,
+    File,
+    Env,
+}
+
+impl Source {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Source::Default => "default",
+            Source::File => "file",
+            Source::Env => "env",
+        }
+    }
+}
+
+/// Fully resolved settings: built-in defaults, overridden by the config file,
+/// then by `SILVER_TASKS_*` environment variables. CLI flags are applied on top by the caller.
+#[derive(Debug, Clone)]
+pub struct Settings {
+    pub default_tag: Option<String>,
+    pub sort: SortOrder,
+    pub color: ColorChoice,
+    pub data_dir: Option<PathBuf>,
+    pub date_input_format: Option<String>,
+    pub date_output_format: String,
+    pub urgency: UrgencyWeights,
+    pub workload: WorkloadLimits,
+    pub backup: BackupRetention,
+    /// List styles and layout; its date format is `date_output_format`
+    pub theme: Theme,
+    /// Template used by `list` when no `--format` is given
+    pub list_format: Option<Template>,
+    /// Named list templates
+    formats: BTreeMap<String, Template>,
+    /// Merged config file and environment values, for `config get/list`
+    table: toml::Table,
+    /// Keys present in the config file
+    file: toml::Table,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self::from_tables(toml::Table::new(), toml::Table::new())
+            .expect("built-in defaults are valid")
+    }
+}
+
+impl Settings {
+    /// Load the config file and environment overrides
+    pub fn load() -> Result<Self> {
+        let file = read_table(&config_file_path()?)?;
+        let mut table = file.clone();
+        apply_env(&mut table)?;
+        Self::from_tables(table, file)
+    }
+
+    /// Validate merged values and build settings from them
+    fn from_tables(table: toml::Table, file: toml::Table) -> Result<Self> {
+        let config = from_table(table.clone())?;
+
+        let sort = match &config.sort {
+            Some(s) => SortOrder::from_str(s, true).map_err(|_| {
+                TaskError::validation(format!(
+                    "Unknown sort order \"{}\" (expected deadline, urgency, id, name, tag or status)",
+                    s
+                ))
+            })?,
+            None => SortOrder::default(),
+        };
+        let color = match &config.color {
+            Some(c) => ColorChoice::from_str(c, true).map_err(|_| {
+                TaskError::validation(format!(
+                    "Unknown color mode \"{}\" (expected auto, always or never)",
+                    c
+                ))
+            })?,
+            None => ColorChoice::default(),
+        };
+
+        if let Some(format) = &config.dates.input {
+            theme::validate_date_format(format)?;
+        }
+        let date_output_format = config
+            .dates
+            .output
+            .clone()
+            .or_else(|| config.theme.date_format.clone())
+            .unwrap_or_else(|| DEFAULT_DATE_FORMAT.to_string());
+        theme::validate_date_format(&date_output_format)?;
+
+        if config.default_tag.as_deref() == Some("") {
+            return Err(TaskError::validation("default_tag cannot be empty").into());
+        }
+
+        for (key, value) in [
+            ("workload.warn_at", config.workload.warn_at),
+            ("workload.day_limit", config.workload.day_limit),
+            ("workload.week_limit", config.workload.week_limit),
+        ] {
+            if value == 0 {
+                return Err(TaskError::validation(format!("{} must be at least 1", key)).into());
+            }
+        }
+
+        let formats = config
+            .formats
+            .iter()
+            .map(|(name, template)| Ok((name.clone(), Template::parse(template)?)))
+            .collect::<Result<BTreeMap<_, _>, TaskError>>()?;
+        let list_format = match &config.list_format {
+            Some(format) => Some(resolve_format(&formats, format)?),
+            None => None,
+        };
+
+        let mut theme = Theme::from_config(&config.theme)?;
+        theme.date_format = date_output_format.clone();
+
+        Ok(Self {
+            default_tag: config.default_tag,
+            sort,
+            color,
+            data_dir: config.data_dir.as_deref().map(expand_tilde),
+            date_input_format: config.dates.input,
+            date_output_format,
+            urgency: config.urgency,
+            workload: config.workload,
+            backup: config.backup,
+            theme,
+            list_format,
+            formats,
+            table,
+            file,
+        })
+    }
+
+    /// Look up a named format, or parse `format` as a template if it contains a placeholder
+    pub fn template(&self, format: &str) -> Result<Template, TaskError> {
+        resolve_format(&self.formats, format)
+    }
+
+    /// Effective value of a key as text, or None when it is unset and has no default
+    pub fn get(&self, key: &str) -> Result<Option<String>> {
+        let (path, _) = key_path(key)?;
+        if let Some(value) = lookup(&self.table, &path) {
+            return Ok(Some(display_value(value)));
+        }
+
+        let defaults = Settings::default();
+        Ok(match key {
+            "sort" => self
+                .sort
+                .to_possible_value()
+                .map(|v| v.get_name().to_string()),
+            "color" => self
+                .color
+                .to_possible_value()
+                .map(|v| v.get_name().to_string()),
+            "dates.output" => Some(self.date_output_format.clone()),
+            "urgency.overdue" => Some(display_value(&defaults.urgency.overdue.into())),
+            "urgency.due_today" => Some(display_value(&defaults.urgency.due_today.into())),
+            "urgency.due_week" => Some(display_value(&defaults.urgency.due_week.into())),
+            "workload.window_days" => Some(defaults.workload.window_days.to_string()),
+            "workload.warn_at" => Some(defaults.workload.warn_at.to_string()),
+            "workload.day_limit" => Some(defaults.workload.day_limit.to_string()),
+            "workload.week_limit" => Some(defaults.workload.week_limit.to_string()),
+            "backup.keep" => Some(defaults.backup.keep.to_string()),
+            "backup.daily_days" => Some(defaults.backup.daily_days.to_string()),
+            "theme.name" => Some("dark".to_string()),
+            "theme.indent" => Some(defaults.theme.indent.to_string()),
+            "theme.padding" => Some(defaults.theme.padding.to_string()),
+            _ => None,
+        })
+    }
+
+    /// Where the effective value of a key comes from
+    pub fn source(&self, key: &str) -> Source {
+        let in_file = key_path(key).is_ok_and(|(path, _)| lookup(&self.file, &path).is_some())
+            // The deprecated `theme.date_format` alias only comes from the config file
+            || (key == "dates.output" && lookup(&self.file, &["theme", "date_format"]).is_some());
+        let from_env =
+            KEYS.iter().any(|(k, _)| *k == key) && std::env::var_os(env_var_name(key)).is_some();
+        if from_env {
+            Source::Env
+        } else if in_file {
+            Source::File
+        } else {
+            Source::Default
+        }
+    }
+
+    /// Every key with an effective value, in `config list` order: fixed keys,
+    /// then per-tag keys
+    pub fn entries(&self) -> Vec<(String, String, Source)> {
+        let mut keys: Vec<String> = KEYS.iter().map(|(k, _)| k.to_string()).collect();
+        for &(prefix, _) in NAMED_KEYS {
+            let path: Vec<&str> = prefix.trim_end_matches('.').split('.').collect();
+            if let Some(tags) = lookup(&self.table, &path).and_then(|v| v.as_table()) {
+                keys.extend(tags.keys().map(|tag| format!("{}{}", prefix, tag)));
+            }
+        }
+
+        keys.into_iter()
+            .filter_map(|key| {
+                let value = self.get(&key).ok().flatten()?;
+                let source = self.source(&key);
+                Some((key, value, source))
+            })
+            .collect()
+    }
+}
+
+/// Resolve a `--format` or `list_format` value: a named format, or an inline template
+fn resolve_format(
+    formats: &BTreeMap<String, Template>,
+    format: &str,
+) -> Result<Template, TaskError> {
+    if let Some(template) = formats.get(format) {
+        return Ok(template.clone());
+    }
+    if !format.contains('{') {
+        return Err(TaskError::validation(format!(
+            "Unknown format \"{}\" (not a named format or a template)",
+            format
+        )));
+    }
+    Template::parse(format)
+}
+
+/// Format a TOML value for `config get/list`: strings without quotes
+fn display_value(value: &toml::Value) -> String {
+    match value {
+        toml::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Expand a leading `~` to the home directory
+fn expand_tilde(path: &str) -> PathBuf {
+    match (path.strip_prefix('~'), dirs::home_dir()) {
+        (Some(rest), Some(home)) if rest.is_empty() || rest.starts_with('/') => {
+            home.join(rest.trim_start_matches('/'))
+        }
+        _ => PathBuf::from(path),
+    }
+}
+
+/// Set a key in the config file contents, keeping comments and formatting.
+/// The result is validated before it is returned, so a bad value never reaches the file.
+fn set_in_document(content: &str, key: &str, raw: &str) -> Result<String> {
+    let (path, kind) = key_path(key)?;
+    let value = match typed_value(key, raw, kind)? {
+        toml::Value::String(s) => toml_edit::value(s),
+        toml::Value::Float(f) => toml_edit::value(f),
+        toml::Value::Integer(i) => toml_edit::value(i),
+        _ => unreachable!("config values are strings or numbers"),
+    };
+
+    let mut doc: toml_edit::DocumentMut = content.parse().map_err(|e: toml_edit::TomlError| {
+        TaskError::validation(format!("Invalid config file: {}", e.message()))
+    })?;
+
+    // Missing parent tables are created implicit, so only the innermost one gets a header
+    let (last, parents) = path.split_last().expect("config keys are never empty");
+    let mut table: &mut dyn toml_edit::TableLike = doc.as_table_mut();
+    for part in parents {
+        if table.get(part).is_none() {
+            let mut new_table = toml_edit::Table::new();
+            new_table.set_implicit(true);
+            table.insert(part, toml_edit::Item::Table(new_table));
+        }
+        table = table
+            .get_mut(part)
+            .and_then(|item| item.as_table_like_mut())
+            .ok_or_else(|| {
+                TaskError::validation(format!("Invalid config file: {} is not a table", part))
+            })?;
+    }
+    table.insert(last, value);
+
+    let updated = doc.to_string();
+    let table = parse_table(&updated)?;
+    Settings::from_tables(table.clone(), table)?;
+    Ok(updated)
+}
+
+/// Set a key in the config file. The file is replaced atomically, so an interrupted
+/// write never leaves a truncated config behind.
+pub fn set(key: &str, value: &str, dry_run: bool) -> Result<PathBuf> {
+    let path = config_file_path()?;
+    let content = if path.exists() {
+        fs::read_to_string(&path)
+            .map_err(|e| TaskError::storage("Failed to read config file", e))?
+    } else {
+        String::new()
+    };
+
+    let updated = set_in_document(&content, key, value)?;
+    if dry_run {
+        return Ok(path);
+    }
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .map_err(|e| TaskError::storage("Failed to create config directory", e))?;
+    }
+    let tmp = path.with_extension("toml.tmp");
+    fs::write(&tmp, updated).map_err(|e| TaskError::storage("Failed to write config file", e))?;
+    fs::rename(&tmp, &path).map_err(|e| TaskError::storage("Failed to save config file", e))?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(content: &str) -> Result<Config> {
+        from_table(parse_table(content)?)
+    }
+
+    #[test]
+    fn test_parse_theme_section() {
+        let config = parse(
+            r#"
+            [theme]
+            name = "light"
+            indent = 2
+
+            [theme.styles]
+            overdue = "bold red"
+
+            [theme.tags]
+            Calculus = "blue"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.theme.name.as_deref(), Some("light"));
+        assert_eq!(config.theme.indent, Some(2));
+        assert_eq!(config.theme.padding, None);
+        assert_eq!(config.theme.styles.overdue.as_deref(), Some("bold red"));
+        assert_eq!(config.theme.tags["Calculus"], "blue");
+    }
+
+    #[test]
+    fn test_parse_empty_and_unknown_keys() {
+        assert!(parse("").unwrap().theme.name.is_none());
+        assert!(parse("[theme]\ncolour = \"red\"").is_err());
+    }
+
+    #[test]
+    fn test_settings_defaults_and_file_values() {
+        let defaults = Settings::default();
+        assert_eq!(defaults.sort, SortOrder::Deadline);
+        assert_eq!(defaults.date_output_format, "%d.%m.%Y");
+        assert_eq!(defaults.get("theme.indent").unwrap().as_deref(), Some("4"));
+        assert_eq!(defaults.get("default_tag").unwrap(), None);
+
+        let table = parse_table(
+            r#"
+            sort = "urgency"
+            default_tag = "Inbox"
+            [dates]
+            output = "%Y-%m-%d"
+            "#,
+        )
+        .unwrap();
+        let settings = Settings::from_tables(table.clone(), table).unwrap();
+        assert_eq!(settings.sort, SortOrder::Urgency);
+        assert_eq!(settings.theme.date_format, "%Y-%m-%d");
+        assert_eq!(
+            settings.get("default_tag").unwrap().as_deref(),
+            Some("Inbox")
+        );
+        assert_eq!(settings.source("sort"), Source::File);
+        assert_eq!(settings.source("color"), Source::Default);
+    }
+
+    #[test]
+    fn test_settings_theme_date_format_alias() {
+        let table = parse_table("[theme]\ndate_format = \"%a %d %b\"").unwrap();
+        let settings = Settings::from_tables(table.clone(), table).unwrap();
+        assert_eq!(settings.date_output_format, "%a %d %b");
+        assert_eq!(settings.theme.date_format, "%a %d %b");
+        assert_eq!(
+            settings.get("dates.output").unwrap().as_deref(),
+            Some("%a %d %b")
+        );
+        assert_eq!(settings.source("dates.output"), Source::File);
+
+        let table =
+            parse_table("[dates]\noutput = \"%Y-%m-%d\"\n[theme]\ndate_format = \"%a %d %b\"")
+                .unwrap();
+        let settings = Settings::from_tables(table.clone(), table).unwrap();
+        assert_eq!(settings.date_output_format, "%Y-%m-%d");
+    }
+
+    #[test]
+    fn test_settings_reject_invalid_values() {
+        for content in [
+            "sort = \"priority\"",
+            "color = \"sometimes\"",
+            "[dates]\noutput = \"%Q\"",
+            "default_tag = \"\"",
+            "[workload]\nwarn_at = 0",
+        ] {
+            let table = parse_table(content).unwrap();
+            assert!(
+                Settings::from_tables(table.clone(), table).is_err(),
+                "{}",
+                content
+            );
+        }
+    }
+
+    #[test]
+    fn test_key_path() {
+        assert_eq!(key_path("dates.output").unwrap().0, vec!["dates", "output"]);
+        assert_eq!(
+            key_path("theme.tags.Lab 1.2").unwrap().0,
+            vec!["theme", "tags", "Lab 1.2"]
+        );
+        assert_eq!(key_path("urgency.tags.Lab").unwrap().1, ValueKind::Number);
+        assert!(key_path("theme.tags.").is_err());
+        assert!(key_path("colour").is_err());
+    }
+
+    #[test]
+    fn test_set_in_document_keeps_comments() {
+        let content = "# my settings\nsort = \"id\" # by id\n";
+        let updated = set_in_document(content, "theme.indent", "2").unwrap();
+        assert!(updated.contains("# my settings"));
+        assert!(updated.contains("[theme]\nindent = 2"));
+        assert!(updated.contains("sort = \"id\" # by id"));
+        assert_eq!(parse(&updated).unwrap().theme.indent, Some(2));
+
+        let updated = set_in_document(&updated, "urgency.tags.Lab", "3").unwrap();
+        assert_eq!(parse(&updated).unwrap().urgency.tags["Lab"], 3.0);
+    }
+
+    #[test]
+    fn test_set_in_document_rejects_bad_values() {
+        assert!(set_in_document("", "theme.indent", "wide").is_err());
+        assert!(set_in_document("", "sort", "random").is_err());
+        assert!(set_in_document("", "nope", "1").is_err());
+        assert!(set_in_document("theme = 1", "theme.indent", "2").is_err());
+    }
+
+    #[test]
+    fn test_named_formats() {
+        let table = parse_table(
+            r#"
+            list_format = "compact"
+            [formats]
+            compact = "{id} {name}"
+            "#,
+        )
+        .unwrap();
+        let settings = Settings::from_tables(table.clone(), table).unwrap();
+        let compact = Template::parse("{id} {name}").unwrap();
+        assert_eq!(settings.list_format.as_ref(), Some(&compact));
+        assert_eq!(settings.template("compact").unwrap(), compact);
+        assert!(settings.template("{tag}").is_ok());
+        assert!(settings.template("wide").is_err());
+
+        for content in ["list_format = \"wide\"", "[formats]\nbad = \"{nope}\""] {
+            let table = parse_table(content).unwrap();
+            assert!(
+                Settings::from_tables(table.clone(), table).is_err(),
+                "{}",
+                content
+            );
+        }
+    }
+
+    #[test]
+    fn test_urgency_score() {
+        let today = NaiveDate::from_ymd_opt(2026, 2, 17).unwrap();
+        let weights = UrgencyWeights {
+            tags: BTreeMap::from([("Lab".to_string(), 2.0)]),
+            ..UrgencyWeights::default()
+        };
+        let task = |days: i64, tag: &str| {
+            Task::new(
+                1,
+                "T".to_string(),
+                tag.to_string(),
+                Some(today + chrono::Duration::days(days)),
+            )
+        };
+
+        assert_eq!(weights.score(&task(-1, "X"), today), 12.0);
+        assert_eq!(weights.score(&task(0, "X"), today), 8.0);
+        assert_eq!(weights.score(&task(7, "Lab"), today), 6.0);
+        assert_eq!(weights.score(&task(8, "X"), today), 0.0);
+
+        let mut done = task(-1, "Lab");
+        done.completed = true;
+        assert_eq!(weights.score(&done, today), 0.0);
+    }
+
+    #[test]
+    fn test_workload_nearby() {
+        let day = |d: u32| NaiveDate::from_ymd_opt(2026, 2, d).unwrap();
+        let mut tasks: Vec<Task> = [13, 14, 15, 17, 18]
+            .iter()
+            .enumerate()
+            .map(|(i, &d)| Task::new(i as u32 + 1, "T".to_string(), "X".to_string(), Some(day(d))))
+            .collect();
+        tasks[3].completed = true;
+        tasks.push(Task::new(6, "T".to_string(), "X".to_string(), None));
+
+        let limits = WorkloadLimits::default();
+        let ids: Vec<u32> = limits
+            .nearby(&tasks, 3, day(15))
+            .iter()
+            .map(|t| t.id)
+            .collect();
+        assert_eq!(ids, vec![1, 2]);
+        let ids: Vec<u32> = limits
+            .nearby(&tasks, 0, day(16))
+            .iter()
+            .map(|t| t.id)
+            .collect();
+        assert_eq!(ids, vec![2, 3, 5]);
+    }
+}
@@ -0,0 +1,178 @@
+use std::fmt;
+
+/// Exit code for unexpected errors without a more specific category
+pub const EXIT_FAILURE: i32 = 1;
+/// Exit code for invalid user input (clap also uses 2 for usage errors)
+pub const EXIT_VALIDATION: i32 = 3;
+/// Exit code when none of the requested tasks exist
+pub const EXIT_NOT_FOUND: i32 = 4;
+/// Exit code when some requested tasks were processed and others were not found
+pub const EXIT_PARTIAL: i32 = 5;
+/// Exit code for failures reading or writing storage files
+pub const EXIT_STORAGE: i32 = 6;
+/// Exit code when another process holds the storage lock
+pub const EXIT_LOCKED: i32 = 7;
+
+/// Errors reported to the user, each mapped to a distinct exit code
+#[derive(Debug)]
+pub enum TaskError {
+    /// Input failed validation (bad date, empty name, malformed ID, ...)
+    Validation(String),
+    /// Requested IDs do not exist. `partial` is set when other requested IDs were processed.
+    NotFound { ids: Vec<String>, partial: bool },
+    /// Some items of a batch, such as import rows, were processed and others failed
+    PartialSuccess(String),
+    /// Reading or writing a storage file failed
+    Storage {
+        message: String,
+        source: Option<Box<dyn std::error::Error + Send + Sync>>,
+    },
+    /// Another silver-tasks process is modifying the task files
+    LockContention,
+}
+
+impl TaskError {
+    /// Create a validation error
+    pub fn validation(message: impl Into<String>) -> Self {
+        TaskError::Validation(message.into())
+    }
+
+    /// Create a storage error caused by an underlying I/O or parse error
+    pub fn storage<E>(message: impl Into<String>, source: E) -> Self
+    where
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        TaskError::Storage {
+            message: message.into(),
+            source: Some(Box::new(source)),
+        }
+    }
+
+    /// Process exit code for this error
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            TaskError::Validation(_) => EXIT_VALIDATION,
+            TaskError::NotFound { partial: false, .. } => EXIT_NOT_FOUND,
+            TaskError::NotFound { partial: true, .. } | TaskError::PartialSuccess(_) => {
+                EXIT_PARTIAL
+            }
+            TaskError::Storage { .. } => EXIT_STORAGE,
+            TaskError::LockContention => EXIT_LOCKED,
+        }
+    }
+
+    /// Stable machine-readable name of the error category
+    pub fn kind(&self) -> &'static str {
+        match self {
+            TaskError::Validation(_) => "validation",
+            TaskError::NotFound { partial: false, .. } => "not_found",
+            TaskError::NotFound { partial: true, .. } | TaskError::PartialSuccess(_) => {
+                "partial_success"
+            }
+            TaskError::Storage { .. } => "storage",
+            TaskError::LockContention => "lock_contention",
+        }
+    }
+}
+
+impl fmt::Display for TaskError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TaskError::Validation(message) | TaskError::PartialSuccess(message) => {
+                write!(f, "{}", message)
+            }
+            TaskError::NotFound { ids, .. } => {
+                write!(f, "One or more IDs could not be found: {}", ids.join(", "))
+            }
+            TaskError::Storage { message, .. } => write!(f, "{}", message),
+            TaskError::LockContention => write!(
+                f,
+                "Task files are locked by another silver-tasks process, try again"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for TaskError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            TaskError::Storage {
+                source: Some(source),
+                ..
+            } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+/// Find the typed error behind an error chain, if there is one
+pub fn find_task_error(err: &anyhow::Error) -> Option<&TaskError> {
+    err.chain().find_map(|e| e.downcast_ref::<TaskError>())
+}
+
+/// Exit code for any error returned from a command
+pub fn exit_code(err: &anyhow::Error) -> i32 {
+    find_task_error(err).map_or(EXIT_FAILURE, TaskError::exit_code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exit_codes_are_distinct() {
+        let codes = [
+            TaskError::validation("bad").exit_code(),
+            TaskError::NotFound {
+                ids: vec!["7".to_string()],
+                partial: false,
+            }
+            .exit_code(),
+            TaskError::NotFound {
+                ids: vec!["7".to_string()],
+                partial: true,
+            }
+            .exit_code(),
+            TaskError::Storage {
+                message: "disk".to_string(),
+                source: None,
+            }
+            .exit_code(),
+            TaskError::LockContention.exit_code(),
+        ];
+        for (i, a) in codes.iter().enumerate() {
+            assert_ne!(*a, EXIT_FAILURE);
+            for b in &codes[i + 1..] {
+                assert_ne!(a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_partial_success() {
+        let err = TaskError::PartialSuccess("1 of 2 rows could not be imported".to_string());
+        assert_eq!(err.exit_code(), EXIT_PARTIAL);
+        assert_eq!(err.kind(), "partial_success");
+        assert_eq!(err.to_string(), "1 of 2 rows could not be imported");
+    }
+
+    #[test]
+    fn test_exit_code_through_context() {
+        let err = anyhow::Error::new(TaskError::validation("Task name cannot be empty"))
+            .context("Failed to add task");
+        assert_eq!(exit_code(&err), EXIT_VALIDATION);
+        assert_eq!(exit_code(&anyhow::anyhow!("other")), EXIT_FAILURE);
+    }
+
+    #[test]
+    fn test_not_found_message() {
+        let err = TaskError::NotFound {
+            ids: vec!["002".to_string(), "004".to_string()],
+            partial: true,
+        };
+        assert_eq!(
+            err.to_string(),
+            "One or more IDs could not be found: 002, 004"
+        );
+    }
+}
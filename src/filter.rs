@@ -0,0 +1,124 @@
+use crate::commands::add::parse_date;
+use crate::error::TaskError;
+use crate::models::Task;
+use crate::template;
+use chrono::NaiveDate;
+
+/// One `--filter` condition
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Condition {
+    Tag(String),
+    /// A `template::status` value, or "active" for any task not completed
+    Status(String),
+    /// Case-insensitive substring of the name
+    Name(String),
+    DueBefore(NaiveDate),
+    DueAfter(NaiveDate),
+}
+
+/// Task filter built from `--filter key=value` expressions, all of which must match.
+///
+/// Keys are `tag`, `status` (open, today, overdue, done or active), `name` (case-insensitive
+/// substring), and `due_before` / `due_after` (inclusive dates; tasks without deadline never
+/// match).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TaskFilter {
+    conditions: Vec<Condition>,
+}
+
+impl TaskFilter {
+    /// Parse filter expressions. Dates accept the same formats as `add`.
+    pub fn parse(exprs: &[String], input_format: Option<&str>) -> Result<Self, TaskError> {
+        let conditions = exprs
+            .iter()
+            .map(|expr| parse_condition(expr, input_format))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { conditions })
+    }
+
+    /// Whether a task satisfies every condition on `today`
+    pub fn matches(&self, task: &Task, today: NaiveDate) -> bool {
+        self.conditions.iter().all(|c| match c {
+            Condition::Tag(tag) => &task.tag == tag,
+            Condition::Status(status) if status == "active" => !task.completed,
+            Condition::Status(status) => template::status(task, today) == status,
+            Condition::Name(text) => task.name.to_lowercase().contains(text),
+            Condition::DueBefore(date) => task.deadline.is_some_and(|d| d <= *date),
+            Condition::DueAfter(date) => task.deadline.is_some_and(|d| d >= *date),
+        })
+    }
+}
+
+/// Parse one `key=value` expression
+fn parse_condition(expr: &str, input_format: Option<&str>) -> Result<Condition, TaskError> {
+    let invalid =
+        |reason: &str| TaskError::validation(format!("Invalid filter \"{}\": {}", expr, reason));
+    let (key, value) = expr
+        .split_once('=')
+        .ok_or_else(|| invalid("expected key=value"))?;
+    let value = value.trim();
+    let date = || parse_date(value, input_format).map_err(|e| invalid(&e.to_string()));
+
+    Ok(match key.trim() {
+        "tag" => Condition::Tag(value.to_string()),
+        "status" => match value {
+            "open" | "today" | "overdue" | "done" | "active" => {
+                Condition::Status(value.to_string())
+            }
+            _ => return Err(invalid("status is open, today, overdue, done or active")),
+        },
+        "name" => Condition::Name(value.to_lowercase()),
+        "due_before" => Condition::DueBefore(date()?),
+        "due_after" => Condition::DueAfter(date()?),
+        _ => {
+            return Err(invalid(
+                "unknown key (expected tag, status, name, due_before or due_after)",
+            ))
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::testing::date;
+
+    fn filter(exprs: &[&str]) -> TaskFilter {
+        let exprs: Vec<String> = exprs.iter().map(|s| s.to_string()).collect();
+        TaskFilter::parse(&exprs, None).unwrap()
+    }
+
+    #[test]
+    fn test_filter_matches() {
+        let today = date(17);
+        let lab = Task::new(
+            1,
+            "Lab Report".to_string(),
+            "Physics".to_string(),
+            Some(date(16)),
+        );
+        let mut essay = Task::new(2, "Essay".to_string(), "Writing".to_string(), None);
+        essay.completed = true;
+
+        assert!(TaskFilter::default().matches(&lab, today));
+        assert!(filter(&["tag=Physics", "status=overdue"]).matches(&lab, today));
+        assert!(!filter(&["tag=Physics"]).matches(&essay, today));
+        assert!(filter(&["status=done"]).matches(&essay, today));
+        assert!(!filter(&["status=active"]).matches(&essay, today));
+        assert!(filter(&["name=report"]).matches(&lab, today));
+        assert!(filter(&["due_before=16.02.2026"]).matches(&lab, today));
+        assert!(!filter(&["due_after=2026-02-17"]).matches(&lab, today));
+        assert!(!filter(&["due_before=2026-03-01"]).matches(&essay, today));
+    }
+
+    #[test]
+    fn test_filter_parse_errors() {
+        for bad in ["tag", "colour=red", "status=late", "due_before=soon"] {
+            assert!(
+                TaskFilter::parse(&[bad.to_string()], None).is_err(),
+                "{}",
+                bad
+            );
+        }
+    }
+}
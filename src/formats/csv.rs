@@ -0,0 +1,201 @@
+use super::{parse_completed, parse_priority, parse_timestamp, ImportOptions, ImportedRow, FIELDS};
+use crate::commands::add::parse_date;
+use crate::error::TaskError;
+use crate::models::Task;
+use anyhow::Result;
+
+/// Write tasks as CSV: a header row with every task field, then one row per task.
+/// Deadlines are YYYY-MM-DD and timestamps RFC 3339, so spreadsheets and `import` read
+/// them back unambiguously. Extras are a JSON object.
+pub fn export(tasks: &[Task]) -> Result<String> {
+    let mut writer = ::csv::Writer::from_writer(Vec::new());
+    writer.write_record(FIELDS)?;
+    for task in tasks {
+        writer.write_record([
+            task.id.to_string(),
+            task.name.clone(),
+            task.tag.clone(),
+            task.deadline.map(|d| d.to_string()).unwrap_or_default(),
+            task.completed.to_string(),
+            task.created_at.map(|t| t.to_rfc3339()).unwrap_or_default(),
+            task.completed_at
+                .map(|t| t.to_rfc3339())
+                .unwrap_or_default(),
+            task.uid.clone().unwrap_or_default(),
+            task.priority.map(String::from).unwrap_or_default(),
+            if task.extras.is_empty() {
+                String::new()
+            } else {
+                serde_json::to_string(&task.extras)?
+            },
+        ])?;
+    }
+    let bytes = writer.into_inner().map_err(|e| e.into_error())?;
+    Ok(String::from_utf8(bytes)?)
+}
+
+/// Read tasks from CSV with a header row. Columns are matched to task fields by name,
+/// case-insensitively, or through `options.columns`; other columns are ignored.
+/// A `name` column is required. IDs in the file are not kept.
+pub fn import(content: &str, options: &ImportOptions) -> Result<Vec<ImportedRow>> {
+    let mut reader = ::csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(::csv::Trim::All)
+        .from_reader(content.as_bytes());
+
+    let headers = reader
+        .headers()
+        .map_err(|e| TaskError::validation(format!("Invalid CSV header: {}", e)))?
+        .clone();
+    let fields: Vec<Option<&str>> = headers
+        .iter()
+        .map(|column| {
+            let field = options
+                .columns
+                .get(column)
+                .map(String::as_str)
+                .unwrap_or(column);
+            FIELDS
+                .iter()
+                .copied()
+                .find(|f| f.eq_ignore_ascii_case(field))
+        })
+        .collect();
+    if !fields.contains(&Some("name")) {
+        return Err(TaskError::validation("No CSV column maps to the name field").into());
+    }
+
+    let mut rows = Vec::new();
+    for (index, record) in reader.records().enumerate() {
+        let task = record
+            .map_err(|e| format!("Invalid CSV record: {}", e))
+            .and_then(|record| {
+                let values = fields
+                    .iter()
+                    .zip(record.iter())
+                    .filter_map(|(field, value)| Some((field.as_deref()?, value)));
+                read_task(values, options)
+            });
+        rows.push(ImportedRow {
+            row: index + 1,
+            task,
+        });
+    }
+    Ok(rows)
+}
+
+/// Build a task from (field, value) pairs of one record
+fn read_task<'a>(
+    values: impl Iterator<Item = (&'a str, &'a str)>,
+    options: &ImportOptions,
+) -> Result<Task, String> {
+    let mut task = Task::new(0, String::new(), String::new(), None);
+    let mut completed_at = None;
+    for (field, value) in values.filter(|(_, v)| !v.is_empty()) {
+        match field {
+            "name" => task.name = value.to_string(),
+            "tag" => task.tag = value.to_string(),
+            "deadline" => {
+                task.deadline =
+                    Some(parse_date(value, options.date_input_format).map_err(|e| e.to_string())?)
+            }
+            "completed" => task.completed = parse_completed(value)?,
+            "created_at" => task.created_at = Some(parse_timestamp(value)?),
+            "completed_at" => completed_at = Some(parse_timestamp(value)?),
+            "uid" => task.uid = Some(value.to_string()),
+            "priority" => task.priority = Some(parse_priority(value)?),
+            "extras" => {
+                task.extras = serde_json::from_str(value)
+                    .map_err(|e| format!("Invalid extras (expected a JSON object): {}", e))?
+            }
+            _ => {}
+        }
+    }
+    if task.tag.is_empty() {
+        task.tag = options.default_tag.unwrap_or_default().to_string();
+    }
+    if task.completed {
+        task.completed_at = completed_at;
+    }
+    Ok(task)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_export_quotes_fields() {
+        let mut task = Task::new(
+            7,
+            "Read \"Dune\", part 1".to_string(),
+            "Books".to_string(),
+            NaiveDate::from_ymd_opt(2026, 2, 17),
+        );
+        task.completed = true;
+
+        let csv = export(&[task]).unwrap();
+        assert_eq!(
+            csv,
+            "id,name,tag,deadline,completed,created_at,completed_at,uid,priority,extras\n\
+             7,\"Read \"\"Dune\"\", part 1\",Books,2026-02-17,true,,,,,\n"
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut task = Task::new(3, "Lab".to_string(), "Physics".to_string(), None);
+        task.created_at = Some(parse_timestamp("2026-02-01T10:00:00+01:00").unwrap());
+        task.complete(parse_timestamp("2026-02-03T18:30:00+01:00").unwrap());
+        task.uid = Some("20260201T100000000000-3@silver-tasks".to_string());
+        task.priority = Some('B');
+        task.extras
+            .insert("estimate".to_string(), serde_json::json!({"hours": 2}));
+
+        let rows = import(&export(&[task.clone()]).unwrap(), &ImportOptions::default()).unwrap();
+        task.id = 0;
+        assert_eq!(
+            rows,
+            vec![ImportedRow {
+                row: 1,
+                task: Ok(task)
+            }]
+        );
+    }
+
+    #[test]
+    fn test_import_column_mapping_and_row_errors() {
+        let content = "Title,Course,Due,Notes\n\
+                       Essay,Writing,15.02.2026,draft\n\
+                       Lab,,someday,\n\
+                       Quiz\n";
+        let options = ImportOptions {
+            columns: [("Title", "name"), ("Course", "tag"), ("Due", "deadline")]
+                .into_iter()
+                .map(|(c, f)| (c.to_string(), f.to_string()))
+                .collect(),
+            default_tag: Some("Inbox"),
+            ..ImportOptions::default()
+        };
+
+        let rows = import(content, &options).unwrap();
+        let essay = rows[0].task.as_ref().unwrap();
+        assert_eq!(
+            (essay.name.as_str(), essay.tag.as_str()),
+            ("Essay", "Writing")
+        );
+        assert_eq!(essay.deadline, NaiveDate::from_ymd_opt(2026, 2, 15));
+        assert!(rows[1]
+            .task
+            .as_ref()
+            .unwrap_err()
+            .starts_with("Invalid date format"));
+        assert_eq!(rows[2].task.as_ref().unwrap().tag, "Inbox");
+    }
+
+    #[test]
+    fn test_import_requires_name_column() {
+        assert!(import("Title,Tag\nEssay,Writing\n", &ImportOptions::default()).is_err());
+    }
+}
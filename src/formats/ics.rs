@@ -0,0 +1,483 @@
+use super::{generate_uid, ExportOptions, IcsTagSource, ImportOptions, ImportedRow};
+use crate::error::TaskError;
+use crate::models::Task;
+use anyhow::Result;
+use chrono::{DateTime, Duration, Local, NaiveDate, NaiveDateTime, Utc};
+
+/// Identifies the producer of exported calendars
+const PRODID: &str = "-//silver-tasks//silver-tasks 0.1.0//EN";
+/// Longest content line in octets before it is folded (RFC 5545, section 3.1)
+const MAX_LINE_OCTETS: usize = 75;
+/// Summary prefix of completed tasks exported as events, which have no completed status
+const DONE_PREFIX: &str = "[done] ";
+
+/// Write tasks as an RFC 5545 calendar: one VTODO per task, or with `ics_events` one
+/// all-day VEVENT per task with a deadline. Entries carry the task's UID, so calendar
+/// apps update them on re-import instead of adding duplicates. RFC 5545 requires a UID, so
+/// tasks without one, as in a dry-run export, get a new one that is not saved.
+pub fn export(tasks: &[Task], options: &ExportOptions) -> String {
+    let stamp = options.now.with_timezone(&Utc);
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        format!("PRODID:{}", PRODID),
+        "CALSCALE:GREGORIAN".to_string(),
+    ];
+
+    for task in tasks {
+        let uid = format!(
+            "UID:{}",
+            escape_text(&task.uid.clone().unwrap_or_else(generate_uid))
+        );
+        if options.ics_events {
+            let Some(deadline) = task.deadline else {
+                continue;
+            };
+            let summary = if task.completed {
+                format!("{}{}", DONE_PREFIX, task.name)
+            } else {
+                task.name.clone()
+            };
+            lines.push("BEGIN:VEVENT".to_string());
+            lines.push(uid);
+            lines.push(format!("DTSTAMP:{}", utc_timestamp(stamp)));
+            lines.push(format!("SUMMARY:{}", escape_text(&summary)));
+            lines.push(format!("CATEGORIES:{}", escape_text(&task.tag)));
+            lines.push(format!("DTSTART;VALUE=DATE:{}", date_value(deadline)));
+            lines.push(format!(
+                "DTEND;VALUE=DATE:{}",
+                date_value(deadline + Duration::days(1))
+            ));
+            lines.push("TRANSP:TRANSPARENT".to_string());
+            lines.push("END:VEVENT".to_string());
+        } else {
+            lines.push("BEGIN:VTODO".to_string());
+            lines.push(uid);
+            lines.push(format!("DTSTAMP:{}", utc_timestamp(stamp)));
+            if let Some(created) = task.created_at {
+                lines.push(format!(
+                    "CREATED:{}",
+                    utc_timestamp(created.with_timezone(&Utc))
+                ));
+            }
+            lines.push(format!("SUMMARY:{}", escape_text(&task.name)));
+            lines.push(format!("CATEGORIES:{}", escape_text(&task.tag)));
+            if let Some(deadline) = task.deadline {
+                lines.push(format!("DUE;VALUE=DATE:{}", date_value(deadline)));
+            }
+            if task.completed {
+                lines.push("STATUS:COMPLETED".to_string());
+                if let Some(completed) = task.completed_at {
+                    lines.push(format!(
+                        "COMPLETED:{}",
+                        utc_timestamp(completed.with_timezone(&Utc))
+                    ));
+                }
+            } else {
+                lines.push("STATUS:NEEDS-ACTION".to_string());
+            }
+            lines.push("END:VTODO".to_string());
+        }
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    lines.iter().map(|line| fold_line(line) + "\r\n").collect()
+}
+
+/// One content line: name (upper case) and raw value. Parameters are not needed.
+#[derive(Debug, Clone, PartialEq)]
+struct Property {
+    name: String,
+    value: String,
+}
+
+/// A VEVENT or VTODO with its own properties; nested components such as VALARM are dropped
+#[derive(Debug, Clone, PartialEq)]
+struct Entry {
+    kind: String,
+    properties: Vec<Property>,
+}
+
+impl Entry {
+    /// Value of the first property with this name
+    fn get(&self, name: &str) -> Option<&str> {
+        self.properties
+            .iter()
+            .find(|p| p.name == name)
+            .map(|p| p.value.as_str())
+    }
+}
+
+/// Read VEVENT and VTODO entries as tasks. The deadline is DUE, or DTSTART for events and
+/// to-dos without DUE. The tag is the first category or the calendar name
+/// (X-WR-CALNAME), as chosen by `options.ics_tag_from`, falling back to the default tag.
+/// UIDs are kept so repeated imports can be matched to existing tasks.
+pub fn import(content: &str, options: &ImportOptions) -> Result<Vec<ImportedRow>> {
+    let lines = unfold(content);
+    if !lines
+        .first()
+        .is_some_and(|l| l.eq_ignore_ascii_case("BEGIN:VCALENDAR"))
+    {
+        return Err(
+            TaskError::validation("Not an iCalendar file (expected BEGIN:VCALENDAR)").into(),
+        );
+    }
+
+    let mut calendar_name = None;
+    let mut entries = Vec::new();
+    let mut current: Option<Entry> = None;
+    // Components open inside the current entry, such as VALARM
+    let mut nested = 0;
+    for line in &lines {
+        let Some(property) = parse_property(line) else {
+            continue;
+        };
+        let component = property.value.to_uppercase();
+        match (property.name.as_str(), current.as_mut()) {
+            ("BEGIN", None) if component == "VEVENT" || component == "VTODO" => {
+                current = Some(Entry {
+                    kind: component,
+                    properties: Vec::new(),
+                });
+            }
+            ("BEGIN", Some(_)) => nested += 1,
+            ("END", Some(_)) if nested > 0 => nested -= 1,
+            ("END", Some(entry)) if entry.kind == component => {
+                entries.extend(current.take());
+            }
+            (_, Some(entry)) if nested == 0 => entry.properties.push(property),
+            ("X-WR-CALNAME", None) => calendar_name = Some(unescape_text(&property.value)),
+            _ => {}
+        }
+    }
+
+    Ok(entries
+        .iter()
+        .enumerate()
+        .map(|(index, entry)| ImportedRow {
+            row: index + 1,
+            task: read_task(entry, calendar_name.as_deref(), options),
+        })
+        .collect())
+}
+
+/// Build a task from one entry
+fn read_task(
+    entry: &Entry,
+    calendar_name: Option<&str>,
+    options: &ImportOptions,
+) -> Result<Task, String> {
+    let mut task = Task::new(0, String::new(), String::new(), None);
+    task.name = entry.get("SUMMARY").map(unescape_text).unwrap_or_default();
+    task.uid = entry.get("UID").map(unescape_text);
+
+    let date_property = match entry.kind.as_str() {
+        "VTODO" => ["DUE", "DTSTART"].iter().find_map(|n| find(entry, n)),
+        _ => find(entry, "DTSTART"),
+    };
+    task.deadline = date_property.map(date_of).transpose()?;
+
+    let tag = match options.ics_tag_from {
+        IcsTagSource::Categories => entry
+            .get("CATEGORIES")
+            .and_then(|c| split_list(c).into_iter().next()),
+        IcsTagSource::CalendarName => calendar_name.map(str::to_string),
+    };
+    task.tag = tag
+        .filter(|t| !t.is_empty())
+        .or_else(|| options.default_tag.map(str::to_string))
+        .unwrap_or_default();
+
+    if let Some(created) = find(entry, "CREATED") {
+        task.created_at = Some(timestamp_of(created)?);
+    }
+    if entry
+        .get("STATUS")
+        .is_some_and(|s| s.eq_ignore_ascii_case("COMPLETED"))
+    {
+        task.completed = true;
+        task.completed_at = find(entry, "COMPLETED").map(timestamp_of).transpose()?;
+    } else if entry.kind == "VEVENT" {
+        // Events exported with `--events` mark completion in the summary
+        if let Some(name) = task.name.strip_prefix(DONE_PREFIX) {
+            task.name = name.to_string();
+            task.completed = true;
+        }
+    }
+    Ok(task)
+}
+
+/// First property with this name
+fn find<'a>(entry: &'a Entry, name: &str) -> Option<&'a Property> {
+    entry.properties.iter().find(|p| p.name == name)
+}
+
+/// Date of a DATE or DATE-TIME property. UTC times are converted to the local date; times
+/// with a TZID or no zone are taken as local.
+fn date_of(property: &Property) -> Result<NaiveDate, String> {
+    let value = property.value.as_str();
+    let invalid = || format!("Invalid date \"{}\" in {}", value, property.name);
+    if value.len() == 8 {
+        return NaiveDate::parse_from_str(value, "%Y%m%d").map_err(|_| invalid());
+    }
+    let (local, utc) = match value.strip_suffix('Z') {
+        Some(v) => (v, true),
+        None => (value, false),
+    };
+    let time = NaiveDateTime::parse_from_str(local, "%Y%m%dT%H%M%S").map_err(|_| invalid())?;
+    Ok(if utc {
+        time.and_utc().with_timezone(&Local).date_naive()
+    } else {
+        time.date()
+    })
+}
+
+/// Timestamp of a DATE-TIME property such as CREATED or COMPLETED
+fn timestamp_of(property: &Property) -> Result<DateTime<Local>, String> {
+    let value = property.value.as_str();
+    let invalid = || format!("Invalid timestamp \"{}\" in {}", value, property.name);
+    let (local, utc) = match value.strip_suffix('Z') {
+        Some(v) => (v, true),
+        None => (value, false),
+    };
+    let time = NaiveDateTime::parse_from_str(local, "%Y%m%dT%H%M%S").map_err(|_| invalid())?;
+    if utc {
+        Ok(time.and_utc().with_timezone(&Local))
+    } else {
+        time.and_local_timezone(Local)
+            .earliest()
+            .ok_or_else(invalid)
+    }
+}
+
+/// Join folded lines back together, accepting CRLF or LF line endings
+fn unfold(content: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in content.lines() {
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(rest), Some(last)) => last.push_str(rest),
+            _ if line.trim().is_empty() => {}
+            _ => lines.push(line.to_string()),
+        }
+    }
+    lines
+}
+
+/// Split a content line into name and value. The value starts at the first colon outside
+/// a quoted parameter value.
+fn parse_property(line: &str) -> Option<Property> {
+    let mut quoted = false;
+    let colon = line.char_indices().find_map(|(i, c)| match c {
+        '"' => {
+            quoted = !quoted;
+            None
+        }
+        ':' if !quoted => Some(i),
+        _ => None,
+    })?;
+    let name = line[..colon].split(';').next()?.trim().to_uppercase();
+    Some(Property {
+        name,
+        value: line[colon + 1..].to_string(),
+    })
+}
+
+/// Split a list value such as CATEGORIES at unescaped commas and unescape each item
+fn split_list(value: &str) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut item = String::new();
+    let mut escaped = false;
+    for c in value.chars() {
+        match c {
+            ',' if !escaped => items.push(unescape_text(&std::mem::take(&mut item))),
+            _ => item.push(c),
+        }
+        escaped = c == '\\' && !escaped;
+    }
+    items.push(unescape_text(&item));
+    items.into_iter().map(|i| i.trim().to_string()).collect()
+}
+
+/// Undo `escape_text`
+fn unescape_text(s: &str) -> String {
+    let mut text = String::new();
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            text.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n' | 'N') => text.push('\n'),
+            Some(other) => text.push(other),
+            None => {}
+        }
+    }
+    text
+}
+
+/// A DATE value, e.g. 20260217
+fn date_value(date: NaiveDate) -> String {
+    date.format("%Y%m%d").to_string()
+}
+
+/// A DATE-TIME value in UTC, e.g. 20260217T100000Z
+fn utc_timestamp(at: DateTime<Utc>) -> String {
+    at.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// Escape a TEXT value: backslashes, semicolons, commas and newlines
+fn escape_text(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// Fold a content line longer than 75 octets into continuation lines starting with a
+/// space, without splitting UTF-8 characters
+fn fold_line(line: &str) -> String {
+    let mut folded = String::new();
+    let mut octets = 0;
+    for c in line.chars() {
+        if octets + c.len_utf8() > MAX_LINE_OCTETS {
+            folded.push_str("\r\n ");
+            // The leading space counts towards the continuation line
+            octets = 1;
+        }
+        folded.push(c);
+        octets += c.len_utf8();
+    }
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Local, TimeZone};
+
+    fn options(ics_events: bool) -> ExportOptions<'static> {
+        ExportOptions {
+            ics_events,
+            ..ExportOptions::sample()
+        }
+    }
+
+    fn task() -> Task {
+        let mut task = Task::new(
+            7,
+            "Read \"Dune\", part 1; notes".to_string(),
+            "Books".to_string(),
+            NaiveDate::from_ymd_opt(2026, 2, 20),
+        );
+        task.uid = Some("abc@silver-tasks".to_string());
+        task
+    }
+
+    #[test]
+    fn test_export_vtodo() {
+        let mut done = task();
+        done.complete(
+            Utc.with_ymd_and_hms(2026, 2, 18, 12, 0, 0)
+                .unwrap()
+                .with_timezone(&Local),
+        );
+
+        let ics = export(&[task(), done], &options(false));
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        assert!(ics.contains(
+            "BEGIN:VTODO\r\nUID:abc@silver-tasks\r\nDTSTAMP:20260217T093000Z\r\n\
+             SUMMARY:Read \"Dune\"\\, part 1\\; notes\r\nCATEGORIES:Books\r\n\
+             DUE;VALUE=DATE:20260220\r\nSTATUS:NEEDS-ACTION\r\nEND:VTODO\r\n"
+        ));
+        assert!(ics.contains("STATUS:COMPLETED\r\nCOMPLETED:20260218T120000Z\r\n"));
+
+        // Every entry needs a UID, so tasks without one get a new one
+        let mut task = task();
+        task.uid = None;
+        for ics_events in [false, true] {
+            let ics = export(std::slice::from_ref(&task), &options(ics_events));
+            assert_eq!(ics.matches("\r\nUID:").count(), 1);
+        }
+    }
+
+    #[test]
+    fn test_export_events() {
+        let undated = Task::new(8, "Someday".to_string(), "Books".to_string(), None);
+        let ics = export(&[task(), undated], &options(true));
+        assert!(ics.contains("DTSTART;VALUE=DATE:20260220\r\nDTEND;VALUE=DATE:20260221\r\n"));
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 1);
+        assert!(!ics.contains("VTODO"));
+    }
+
+    #[test]
+    fn test_fold_line() {
+        let line = format!("SUMMARY:{}", "é".repeat(40));
+        let folded = fold_line(&line);
+        let parts: Vec<&str> = folded.split("\r\n").collect();
+        assert_eq!(parts.len(), 2);
+        assert!(parts.iter().all(|p| p.len() <= MAX_LINE_OCTETS));
+        assert!(parts[1].starts_with(' '));
+        assert_eq!(folded.replace("\r\n ", ""), line);
+    }
+
+    #[test]
+    fn test_import_lms_feed() {
+        let content = "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nX-WR-CALNAME:Physics 101\r\n\
+                       BEGIN:VEVENT\r\nUID:assign-1@lms\r\nSUMMARY:Lab report\\, part 2\r\n\
+                       CATEGORIES:Labs,Homework\r\nDTSTART;VALUE=DATE:20260305\r\n\
+                       BEGIN:VALARM\r\nSUMMARY:Reminder\r\nEND:VALARM\r\nEND:VEVENT\r\n\
+                       BEGIN:VTODO\r\nUID:assign-2@lms\r\nSUMMARY:Problem set with a very long \r\n \
+                       title\r\nDUE;TZID=Europe/Berlin:20260310T235900\r\nSTATUS:COMPLETED\r\n\
+                       COMPLETED:20260309T120000Z\r\nEND:VTODO\r\nEND:VCALENDAR\r\n";
+
+        let rows = import(content, &ImportOptions::default()).unwrap();
+        let lab = rows[0].task.as_ref().unwrap();
+        assert_eq!(lab.name, "Lab report, part 2");
+        assert_eq!(lab.tag, "Labs");
+        assert_eq!(lab.uid.as_deref(), Some("assign-1@lms"));
+        assert_eq!(lab.deadline, NaiveDate::from_ymd_opt(2026, 3, 5));
+        let set = rows[1].task.as_ref().unwrap();
+        assert_eq!(set.name, "Problem set with a very long title");
+        assert_eq!(set.deadline, NaiveDate::from_ymd_opt(2026, 3, 10));
+        assert!(set.completed && set.completed_at.is_some());
+
+        let options = ImportOptions {
+            ics_tag_from: IcsTagSource::CalendarName,
+            ..ImportOptions::default()
+        };
+        let rows = import(content, &options).unwrap();
+        assert!(rows
+            .iter()
+            .all(|r| r.task.as_ref().unwrap().tag == "Physics 101"));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut done = task();
+        done.complete(Local::now());
+        for ics_events in [false, true] {
+            let ics = export(&[done.clone()], &options(ics_events));
+            let rows = import(&ics, &ImportOptions::default()).unwrap();
+            let imported = rows[0].task.as_ref().unwrap();
+            assert_eq!(imported.name, done.name);
+            assert_eq!(imported.tag, done.tag);
+            assert_eq!(imported.uid, done.uid);
+            assert_eq!(imported.deadline, done.deadline);
+            assert!(imported.completed);
+        }
+    }
+
+    #[test]
+    fn test_import_errors() {
+        assert!(import("id,name\n1,Essay\n", &ImportOptions::default()).is_err());
+        let content =
+            "BEGIN:VCALENDAR\nBEGIN:VTODO\nSUMMARY:Quiz\nDUE:soon\nEND:VTODO\nEND:VCALENDAR\n";
+        let rows = import(content, &ImportOptions::default()).unwrap();
+        assert_eq!(
+            rows[0].task.as_ref().unwrap_err(),
+            "Invalid date \"soon\" in DUE"
+        );
+    }
+}
@@ -0,0 +1,151 @@
+use super::{ExportOptions, ImportOptions, ImportedRow};
+use crate::commands::add::parse_date;
+use crate::commands::list::group_tasks;
+use crate::models::Task;
+
+/// Write a `## Tag` heading per tag, in the order `list` shows them, each followed by a
+/// checklist item per task: `- [ ] name (due DATE)`, or `- [x]` for completed tasks.
+pub fn export(tasks: &[Task], options: &ExportOptions) -> String {
+    let today = options.now.date_naive();
+    let groups = group_tasks(tasks.to_vec(), options.sort, &options.urgency, today);
+    let sections: Vec<String> = groups
+        .into_iter()
+        .map(|(tag, tasks)| {
+            let items: String = tasks
+                .iter()
+                .map(|task| {
+                    let mark = if task.completed { 'x' } else { ' ' };
+                    let due = task
+                        .deadline
+                        .map(|d| format!(" (due {})", d.format(options.date_output_format)))
+                        .unwrap_or_default();
+                    format!("- [{}] {}{}\n", mark, task.name, due)
+                })
+                .collect();
+            format!("## {}\n\n{}", tag, items)
+        })
+        .collect();
+    sections.join("\n")
+}
+
+/// Read every checklist item (`- [ ]`, `- [x]`, also with `*` or `+` bullets and indented)
+/// as a task, ignoring all other lines so notes files can be imported. The tag is the
+/// closest heading above the item, or the default tag before the first heading. A trailing
+/// `(due DATE)` is the deadline, in any format `add` accepts.
+pub fn import(content: &str, options: &ImportOptions) -> Vec<ImportedRow> {
+    let mut heading: Option<&str> = None;
+    let mut rows = Vec::new();
+    for line in content.lines().map(str::trim) {
+        if line.starts_with('#') {
+            let title = line.trim_start_matches('#');
+            if title.starts_with(' ') || title.is_empty() {
+                heading = Some(title.trim()).filter(|t| !t.is_empty());
+            }
+            continue;
+        }
+        let Some((completed, text)) = checklist_item(line) else {
+            continue;
+        };
+        let tag = heading.or(options.default_tag).unwrap_or_default();
+        rows.push(ImportedRow {
+            row: rows.len() + 1,
+            task: read_task(completed, text, tag, options),
+        });
+    }
+    rows
+}
+
+/// Split a checklist line into its completed mark and text
+fn checklist_item(line: &str) -> Option<(bool, &str)> {
+    let rest = line.strip_prefix(['-', '*', '+'])?.trim_start();
+    let (mark, text) = rest.strip_prefix('[')?.split_once(']')?;
+    let completed = match mark {
+        " " | "" => false,
+        "x" | "X" => true,
+        _ => return None,
+    };
+    Some((completed, text.trim()))
+}
+
+/// Build a task from the text of one checklist item
+fn read_task(
+    completed: bool,
+    text: &str,
+    tag: &str,
+    options: &ImportOptions,
+) -> Result<Task, String> {
+    let mut task = Task::new(0, text.to_string(), tag.to_string(), None);
+    task.completed = completed;
+    let due = text.strip_suffix(')').and_then(|t| t.rsplit_once("(due "));
+    if let Some((name, date)) = due {
+        task.deadline =
+            Some(parse_date(date.trim(), options.date_input_format).map_err(|e| e.to_string())?);
+        task.name = name.trim_end().to_string();
+    }
+    Ok(task)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn date(d: u32) -> Option<NaiveDate> {
+        NaiveDate::from_ymd_opt(2026, 3, d)
+    }
+
+    #[test]
+    fn test_export_groups_by_tag() {
+        let mut done = Task::new(3, "Essay".to_string(), "Writing".to_string(), None);
+        done.completed = true;
+        let tasks = [
+            Task::new(1, "Lab".to_string(), "Physics".to_string(), date(5)),
+            done,
+            Task::new(2, "Quiz".to_string(), "Physics".to_string(), date(1)),
+        ];
+        assert_eq!(
+            export(&tasks, &ExportOptions::sample()),
+            "## Physics\n\n\
+             - [ ] Quiz (due 01.03.2026)\n\
+             - [ ] Lab (due 05.03.2026)\n\
+             \n\
+             ## Writing\n\n\
+             - [x] Essay\n"
+        );
+    }
+
+    #[test]
+    fn test_import_notes() {
+        let content = "Notes from Tuesday's meeting\n\
+                       - [ ] Bring snacks\n\
+                       \n\
+                       ## Physics\n\
+                       Some text about the lab.\n\
+                       - [x] Read chapter 2\n  \
+                         * [ ] Lab (draft) (due 05.03.2026)\n\
+                       - not a task\n\
+                       ### Writing\n\
+                       + [ ] Essay (due someday)\n";
+        let options = ImportOptions {
+            default_tag: Some("Inbox"),
+            ..ImportOptions::default()
+        };
+        let rows = import(content, &options);
+        let tasks: Vec<_> = rows.iter().map(|r| r.task.as_ref()).collect();
+        assert_eq!(tasks.len(), 4);
+        let snacks = tasks[0].unwrap();
+        assert_eq!(
+            (snacks.name.as_str(), snacks.tag.as_str()),
+            ("Bring snacks", "Inbox")
+        );
+        assert!(tasks[1].unwrap().completed);
+        let lab = tasks[2].unwrap();
+        assert_eq!(
+            (lab.name.as_str(), lab.tag.as_str()),
+            ("Lab (draft)", "Physics")
+        );
+        assert_eq!(lab.deadline, date(5));
+        assert_eq!(rows[3].row, 4);
+        assert!(tasks[3].unwrap_err().starts_with("Invalid date format"));
+    }
+}
@@ -0,0 +1,203 @@
+mod csv;
+mod ics;
+mod markdown;
+mod org;
+mod taskwarrior;
+mod todotxt;
+
+use crate::config::{SortOrder, UrgencyWeights};
+use crate::models::Task;
+use anyhow::Result;
+use chrono::{DateTime, Local};
+use clap::ValueEnum;
+use std::collections::BTreeMap;
+
+/// Task fields in export column order
+pub(crate) const FIELDS: [&str; 10] = [
+    "id",
+    "name",
+    "tag",
+    "deadline",
+    "completed",
+    "created_at",
+    "completed_at",
+    "uid",
+    "priority",
+    "extras",
+];
+
+/// File format of `export` and `import`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ExchangeFormat {
+    /// Comma-separated values with a header row
+    Csv,
+    /// iCalendar (RFC 5545) with one VTODO per task
+    Ics,
+    /// One todo.txt line per task
+    Todotxt,
+    /// JSON as written by `task export` and read by `task import`
+    Taskwarrior,
+    /// Checklists under a heading per tag
+    Markdown,
+    /// Org-mode TODO headlines under a headline per tag
+    Org,
+}
+
+/// Where ics import takes a task's tag from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum IcsTagSource {
+    /// The entry's first category
+    #[default]
+    Categories,
+    /// The calendar's name (X-WR-CALNAME), e.g. the course of an LMS feed
+    CalendarName,
+}
+
+impl ExchangeFormat {
+    /// Name used in messages and structured output
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ExchangeFormat::Csv => "csv",
+            ExchangeFormat::Ics => "ics",
+            ExchangeFormat::Todotxt => "todotxt",
+            ExchangeFormat::Taskwarrior => "taskwarrior",
+            ExchangeFormat::Markdown => "markdown",
+            ExchangeFormat::Org => "org",
+        }
+    }
+
+    /// Whether exports are read back to sync tasks by UID, so tasks need one before export
+    pub fn syncs_uids(self) -> bool {
+        matches!(
+            self,
+            ExchangeFormat::Ics | ExchangeFormat::Org | ExchangeFormat::Taskwarrior
+        )
+    }
+}
+
+/// Options for writing an export
+#[derive(Debug, Clone)]
+pub struct ExportOptions<'a> {
+    /// Write all-day VEVENTs instead of VTODOs (ics only)
+    pub ics_events: bool,
+    /// Time the export is made, used for calendar timestamps
+    pub now: DateTime<Local>,
+    /// chrono format for deadlines in documents meant for reading (markdown)
+    pub date_output_format: &'a str,
+    /// Order of tasks within each tag group, as in `list` (markdown)
+    pub sort: SortOrder,
+    /// Weights for `sort = urgency`
+    pub urgency: UrgencyWeights,
+}
+
+#[cfg(test)]
+impl ExportOptions<'static> {
+    /// Options shared by the format tests: to-dos, the default date format and order, and a
+    /// fixed export time of 17.02.2026 09:30 UTC
+    pub fn sample() -> Self {
+        use chrono::{TimeZone, Utc};
+        ExportOptions {
+            ics_events: false,
+            now: Utc
+                .with_ymd_and_hms(2026, 2, 17, 9, 30, 0)
+                .unwrap()
+                .with_timezone(&Local),
+            date_output_format: "%d.%m.%Y",
+            sort: SortOrder::default(),
+            urgency: UrgencyWeights::default(),
+        }
+    }
+}
+
+/// Options for reading an import file
+#[derive(Debug, Clone, Default)]
+pub struct ImportOptions<'a> {
+    /// Source column to task field, for columns not named like the field
+    pub columns: BTreeMap<String, String>,
+    /// chrono format tried first for deadlines
+    pub date_input_format: Option<&'a str>,
+    /// Tag for rows without one
+    pub default_tag: Option<&'a str>,
+    /// Where ics entries take their tag from
+    pub ics_tag_from: IcsTagSource,
+}
+
+/// One record of an import file: the task read from it, or why it could not be read.
+/// Tasks still need an ID and validation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedRow {
+    /// Record number in the file, starting at 1 for the first task
+    pub row: usize,
+    pub task: Result<Task, String>,
+}
+
+/// Write tasks in a format
+pub fn export(format: ExchangeFormat, tasks: &[Task], options: &ExportOptions) -> Result<String> {
+    match format {
+        ExchangeFormat::Csv => csv::export(tasks),
+        ExchangeFormat::Ics => Ok(ics::export(tasks, options)),
+        ExchangeFormat::Todotxt => Ok(todotxt::export(tasks, options)),
+        ExchangeFormat::Taskwarrior => taskwarrior::export(tasks),
+        ExchangeFormat::Markdown => Ok(markdown::export(tasks, options)),
+        ExchangeFormat::Org => Ok(org::export(tasks, options)),
+    }
+}
+
+/// Read tasks from file contents. An unreadable file is an error; unreadable records are
+/// reported per row.
+pub fn import(
+    format: ExchangeFormat,
+    content: &str,
+    options: &ImportOptions,
+) -> Result<Vec<ImportedRow>> {
+    match format {
+        ExchangeFormat::Csv => csv::import(content, options),
+        ExchangeFormat::Ics => ics::import(content, options),
+        ExchangeFormat::Todotxt => Ok(todotxt::import(content, options)),
+        ExchangeFormat::Taskwarrior => taskwarrior::import(content, options),
+        ExchangeFormat::Markdown => Ok(markdown::import(content, options)),
+        ExchangeFormat::Org => Ok(org::import(content, options)),
+    }
+}
+
+/// Give every task without a UID a new one, so repeated exports identify the same entries.
+/// Returns whether any task changed.
+pub fn assign_uids<'a>(tasks: impl IntoIterator<Item = &'a mut Task>) -> bool {
+    let mut changed = false;
+    for task in tasks.into_iter().filter(|t| t.uid.is_none()) {
+        task.uid = Some(generate_uid());
+        changed = true;
+    }
+    changed
+}
+
+/// A new random version 4 UUID, e.g. "3f2b8c1e-5d7a-4e09-9b6f-0c4d2a8e7f13", which calendar
+/// apps and Taskwarrior both accept as an identifier
+fn generate_uid() -> String {
+    uuid::Uuid::new_v4().to_string()
+}
+
+/// Parse an RFC 3339 timestamp, e.g. "2026-02-17T10:00:00+01:00"
+fn parse_timestamp(value: &str) -> Result<DateTime<Local>, String> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|t| t.with_timezone(&Local))
+        .map_err(|_| format!("Invalid timestamp \"{}\" (expected RFC 3339)", value))
+}
+
+/// Parse a priority letter, A to Z in either case
+fn parse_priority(value: &str) -> Result<char, String> {
+    let mut chars = value.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) if c.is_ascii_alphabetic() => Ok(c.to_ascii_uppercase()),
+        _ => Err(format!("Invalid priority \"{}\" (expected A to Z)", value)),
+    }
+}
+
+/// Parse a completed flag: true/false, yes/no, 1/0 or x
+fn parse_completed(value: &str) -> Result<bool, String> {
+    match value.to_lowercase().as_str() {
+        "true" | "yes" | "1" | "x" => Ok(true),
+        "false" | "no" | "0" => Ok(false),
+        _ => Err(format!("Invalid completed value \"{}\"", value)),
+    }
+}
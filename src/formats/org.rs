@@ -0,0 +1,328 @@
+use super::{ExportOptions, ImportOptions, ImportedRow};
+use crate::commands::list::group_tasks;
+use crate::models::Task;
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime};
+
+/// Org date of a DEADLINE timestamp, e.g. 2026-02-15 Sun
+const DATE_FORMAT: &str = "%Y-%m-%d %a";
+/// Org date and time of CLOSED and CREATED timestamps, e.g. 2026-02-15 Sun 14:30
+const TIME_FORMAT: &str = "%Y-%m-%d %a %H:%M";
+
+/// Write a level-1 headline per tag, in the order `list` shows them, with a level-2
+/// `TODO` or `DONE` headline per task:
+///
+/// ```text
+/// * Physics
+/// ** TODO [#A] Lab report :Physics:
+///    CLOSED: [2026-02-20 Fri 14:30] DEADLINE: <2026-02-15 Sun>
+///    :PROPERTIES:
+///    :ID:       6a1b2c3d-4e5f-4a7b-8c9d-0e1f2a3b4c5d
+///    :CREATED:  [2026-02-01 Sun 10:00]
+///    :END:
+/// ```
+///
+/// Characters org does not allow in tags are replaced with `_` in the `:tag:` annotation;
+/// the parent headline has the tag as written.
+pub fn export(tasks: &[Task], options: &ExportOptions) -> String {
+    let today = options.now.date_naive();
+    let groups = group_tasks(tasks.to_vec(), options.sort, &options.urgency, today);
+    let mut out = String::new();
+    for (tag, tasks) in groups {
+        out.push_str(&format!("* {}\n", tag));
+        for task in &tasks {
+            write_task(&mut out, task);
+        }
+    }
+    out
+}
+
+/// Append the headline, planning line and property drawer of one task
+fn write_task(out: &mut String, task: &Task) {
+    let keyword = if task.completed { "DONE" } else { "TODO" };
+    let cookie = task
+        .priority
+        .map(|p| format!("[#{}] ", p))
+        .unwrap_or_default();
+    out.push_str(&format!(
+        "** {} {}{} :{}:\n",
+        keyword,
+        cookie,
+        task.name,
+        org_tag(&task.tag)
+    ));
+
+    let mut planning = Vec::new();
+    if let Some(closed) = task.completed_at.filter(|_| task.completed) {
+        planning.push(format!("CLOSED: [{}]", closed.format(TIME_FORMAT)));
+    }
+    if let Some(deadline) = task.deadline {
+        planning.push(format!("DEADLINE: <{}>", deadline.format(DATE_FORMAT)));
+    }
+    if !planning.is_empty() {
+        out.push_str(&format!("   {}\n", planning.join(" ")));
+    }
+
+    let mut properties = Vec::new();
+    if let Some(uid) = &task.uid {
+        properties.push(("ID", uid.clone()));
+    }
+    if let Some(created) = task.created_at {
+        properties.push(("CREATED", format!("[{}]", created.format(TIME_FORMAT))));
+    }
+    if !properties.is_empty() {
+        out.push_str("   :PROPERTIES:\n");
+        for (name, value) in properties {
+            out.push_str(&format!("   {:<11}{}\n", format!(":{}:", name), value));
+        }
+        out.push_str("   :END:\n");
+    }
+}
+
+/// A tag as an org tag, which may only contain letters, digits, `_`, `@`, `#` and `%`
+fn org_tag(tag: &str) -> String {
+    tag.chars()
+        .map(|c| {
+            if c.is_alphanumeric() || "_@#%".contains(c) {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// A headline: its level, TODO keyword, priority cookie, title and tags
+#[derive(Debug, PartialEq)]
+struct Headline<'a> {
+    level: usize,
+    keyword: Option<&'a str>,
+    priority: Option<char>,
+    title: &'a str,
+    tags: Vec<&'a str>,
+}
+
+/// Read every `TODO` and `DONE` headline as a task, at any level. The tag is the title of
+/// the outermost enclosing headline without a keyword, else the first `:tag:` of the
+/// headline, else the default tag. The planning line gives the deadline and completion
+/// time, and the property drawer the UID (`ID`) and creation time (`CREATED`). Other
+/// headlines and text are ignored.
+pub fn import(content: &str, options: &ImportOptions) -> Vec<ImportedRow> {
+    // Titles of the enclosing headlines, by level
+    let mut parents: Vec<(usize, &str)> = Vec::new();
+    let mut rows: Vec<ImportedRow> = Vec::new();
+    // Whether the lines after the last headline belong to a task
+    let mut in_task = false;
+    for line in content.lines() {
+        if let Some(headline) = parse_headline(line) {
+            parents.retain(|(level, _)| *level < headline.level);
+            in_task = headline.keyword.is_some();
+            if !in_task {
+                parents.push((headline.level, headline.title));
+                continue;
+            }
+            let mut task = Task::new(0, headline.title.to_string(), String::new(), None);
+            task.completed = headline.keyword == Some("DONE");
+            task.priority = headline.priority;
+            task.tag = parents
+                .first()
+                .map(|(_, title)| *title)
+                .or(headline.tags.first().copied())
+                .or(options.default_tag)
+                .unwrap_or_default()
+                .to_string();
+            rows.push(ImportedRow {
+                row: rows.len() + 1,
+                task: Ok(task),
+            });
+            continue;
+        }
+        if !in_task {
+            continue;
+        }
+        if let Some(row) = rows.last_mut() {
+            if let Ok(task) = &mut row.task {
+                if let Err(message) = read_body_line(task, line.trim()) {
+                    row.task = Err(message);
+                }
+            }
+        }
+    }
+    rows
+}
+
+/// Parse a headline: stars, then an optional keyword, priority cookie and trailing tags
+fn parse_headline(line: &str) -> Option<Headline<'_>> {
+    let title = line.trim_start_matches('*');
+    let level = line.len() - title.len();
+    if level == 0 || !(title.is_empty() || title.starts_with(' ')) {
+        return None;
+    }
+    let mut title = title.trim();
+
+    let keyword = ["TODO", "DONE"]
+        .into_iter()
+        .find(|k| title == *k || title.starts_with(&format!("{} ", k)));
+    if let Some(keyword) = keyword {
+        title = title[keyword.len()..].trim_start();
+    }
+    let mut priority = None;
+    if let Some(rest) = title.strip_prefix("[#") {
+        let mut chars = rest.chars();
+        if let (Some(p), Some(']')) = (chars.next(), chars.next()) {
+            priority = Some(p.to_ascii_uppercase());
+            title = chars.as_str().trim_start();
+        }
+    }
+    let mut tags = Vec::new();
+    if let Some((rest, last)) = title.rsplit_once(char::is_whitespace) {
+        if last.len() > 2 && last.starts_with(':') && last.ends_with(':') {
+            tags = last[1..last.len() - 1].split(':').collect();
+            title = rest.trim_end();
+        }
+    }
+    Some(Headline {
+        level,
+        keyword,
+        priority,
+        title,
+        tags,
+    })
+}
+
+/// Apply a planning or property line below a task headline
+fn read_body_line(task: &mut Task, line: &str) -> Result<(), String> {
+    if let Some(value) = property(line, "ID") {
+        task.uid = Some(value.to_string());
+    } else if let Some(value) = property(line, "CREATED") {
+        task.created_at = Some(parse_time(value.trim_matches(['[', ']', '<', '>']))?);
+    } else {
+        if let Some(value) = planning(line, "DEADLINE:") {
+            task.deadline = Some(parse_date(value)?);
+        }
+        if let Some(value) = planning(line, "CLOSED:").filter(|_| task.completed) {
+            task.completed_at = Some(parse_time(value)?);
+        }
+    }
+    Ok(())
+}
+
+/// Value of a `:NAME: value` property line
+fn property<'a>(line: &'a str, name: &str) -> Option<&'a str> {
+    let (key, value) = line.strip_prefix(':')?.split_once(':')?;
+    key.eq_ignore_ascii_case(name).then(|| value.trim())
+}
+
+/// Timestamp after a planning keyword, without its brackets
+fn planning<'a>(line: &'a str, keyword: &str) -> Option<&'a str> {
+    let rest = line[line.find(keyword)? + keyword.len()..].trim_start();
+    let close = match rest.chars().next()? {
+        '<' => '>',
+        '[' => ']',
+        _ => return None,
+    };
+    Some(&rest[1..rest.find(close)?])
+}
+
+/// Date of a timestamp such as "2026-02-15 Sun" or "2026-02-15 Sun 10:00"
+fn parse_date(value: &str) -> Result<NaiveDate, String> {
+    value
+        .get(..10)
+        .and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+        .ok_or_else(|| format!("Invalid org timestamp \"{}\"", value))
+}
+
+/// Local time of a timestamp such as "2026-02-15 Sun 10:00"; without a time, midnight
+fn parse_time(value: &str) -> Result<DateTime<Local>, String> {
+    let date = parse_date(value)?;
+    let time = value
+        .split_whitespace()
+        .find_map(|part| chrono::NaiveTime::parse_from_str(part, "%H:%M").ok())
+        .unwrap_or(chrono::NaiveTime::MIN);
+    NaiveDateTime::new(date, time)
+        .and_local_timezone(Local)
+        .earliest()
+        .ok_or_else(|| format!("Invalid org timestamp \"{}\"", value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn lab() -> Task {
+        let mut task = Task::new(
+            1,
+            "Lab report".to_string(),
+            "Physics 101".to_string(),
+            NaiveDate::from_ymd_opt(2026, 2, 15),
+        );
+        task.priority = Some('A');
+        task.uid = Some("6a1b2c3d-4e5f-4a7b-8c9d-0e1f2a3b4c5d".to_string());
+        task.created_at = Some(Local.with_ymd_and_hms(2026, 2, 1, 10, 0, 0).unwrap());
+        task.complete(Local.with_ymd_and_hms(2026, 2, 14, 14, 30, 0).unwrap());
+        task
+    }
+
+    #[test]
+    fn test_export_headlines() {
+        let quiz = Task::new(2, "Quiz".to_string(), "Math".to_string(), None);
+        assert_eq!(
+            export(&[lab(), quiz], &ExportOptions::sample()),
+            "* Math\n\
+             ** TODO Quiz :Math:\n\
+             * Physics 101\n\
+             ** DONE [#A] Lab report :Physics_101:\n   \
+             CLOSED: [2026-02-14 Sat 14:30] DEADLINE: <2026-02-15 Sun>\n   \
+             :PROPERTIES:\n   \
+             :ID:       6a1b2c3d-4e5f-4a7b-8c9d-0e1f2a3b4c5d\n   \
+             :CREATED:  [2026-02-01 Sun 10:00]\n   \
+             :END:\n"
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let rows = import(
+            &export(&[lab()], &ExportOptions::sample()),
+            &ImportOptions::default(),
+        );
+        let mut expected = lab();
+        expected.id = 0;
+        assert_eq!(rows[0].task, Ok(expected));
+    }
+
+    #[test]
+    fn test_import_outline() {
+        let content = "#+TITLE: Semester\n\
+                       * Chemistry\n\
+                       Some notes.\n\
+                       ** Week 1\n\
+                       *** TODO Titration lab\n    \
+                           DEADLINE: <2026-03-10 Tue>\n\
+                       * TODO Call advisor :admin:\n\
+                       * DONE Buy lab coat\n\
+                       ** TODO Broken\n   \
+                          DEADLINE: <soon>\n";
+        let options = ImportOptions {
+            default_tag: Some("Inbox"),
+            ..ImportOptions::default()
+        };
+        let rows = import(content, &options);
+        assert_eq!(rows.len(), 4);
+        let lab = rows[0].task.as_ref().unwrap();
+        assert_eq!(
+            (lab.name.as_str(), lab.tag.as_str()),
+            ("Titration lab", "Chemistry")
+        );
+        assert_eq!(lab.deadline, NaiveDate::from_ymd_opt(2026, 3, 10));
+        assert_eq!(rows[1].task.as_ref().unwrap().tag, "admin");
+        let coat = rows[2].task.as_ref().unwrap();
+        assert!(coat.completed);
+        assert_eq!(coat.tag, "Inbox");
+        assert!(rows[3]
+            .task
+            .as_ref()
+            .unwrap_err()
+            .starts_with("Invalid org timestamp"));
+    }
+}
@@ -0,0 +1,312 @@
+use super::{ImportOptions, ImportedRow};
+use crate::error::TaskError;
+use crate::models::Task;
+use anyhow::Result;
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use serde_json::{Map, Value};
+
+/// Taskwarrior's date format, always in UTC
+const DATE_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+/// Attributes Taskwarrior computes itself and ignores on import
+const COMPUTED: [&str; 2] = ["id", "urgency"];
+/// Taskwarrior priorities and the priority letters they map to
+const PRIORITIES: [(&str, char); 3] = [("H", 'A'), ("M", 'B'), ("L", 'C')];
+
+/// Write tasks as a JSON array that `task import` accepts, one task per line.
+///
+/// Extras are written first and overridden by mapped fields. The tag becomes `project`,
+/// unless the task came from Taskwarrior with the tag as one of its `tags`. `uuid` is only
+/// written for UIDs that are UUIDs; Taskwarrior assigns one to the others.
+pub fn export(tasks: &[Task]) -> Result<String> {
+    let lines = tasks
+        .iter()
+        .map(|task| serde_json::to_string(&to_json(task)))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(format!("[\n{}\n]\n", lines.join(",\n")))
+}
+
+/// Convert one task to a Taskwarrior object
+fn to_json(task: &Task) -> Map<String, Value> {
+    let mut object: Map<String, Value> = task
+        .extras
+        .iter()
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect();
+    let timestamp =
+        |t: DateTime<Local>| Value::from(t.with_timezone(&Utc).format(DATE_FORMAT).to_string());
+
+    if let Some(uid) = task.uid.as_ref().filter(|uid| is_uuid(uid)) {
+        object.insert("uuid".to_string(), uid.clone().into());
+    }
+    object.insert("description".to_string(), task.name.clone().into());
+
+    // Keep a status Taskwarrior distinguishes, such as deleted or waiting, while it still
+    // agrees with the completed flag
+    let kept_status = object
+        .get("status")
+        .and_then(Value::as_str)
+        .filter(|s| status_completed(s) == Some(task.completed))
+        .map(str::to_string);
+    let status = kept_status.unwrap_or_else(|| {
+        if task.completed {
+            "completed"
+        } else {
+            "pending"
+        }
+        .to_string()
+    });
+    object.insert("status".to_string(), status.into());
+
+    let tags = object.get("tags").and_then(Value::as_array);
+    if !tags.is_some_and(|tags| tags.iter().any(|t| t.as_str() == Some(&task.tag))) {
+        object.insert("project".to_string(), task.tag.clone().into());
+    }
+
+    if let Some(deadline) = task.deadline {
+        object.insert("due".to_string(), timestamp(local_midnight(deadline)));
+    }
+    if let Some(created) = task.created_at {
+        object.insert("entry".to_string(), timestamp(created));
+    }
+    if let Some(completed) = task.completed_at.filter(|_| task.completed) {
+        object.insert("end".to_string(), timestamp(completed));
+    }
+    let priority = PRIORITIES
+        .iter()
+        .find(|(_, letter)| Some(*letter) == task.priority);
+    if let Some((name, _)) = priority {
+        object.insert("priority".to_string(), (*name).into());
+    }
+    object
+}
+
+/// Read `task export` output: a JSON array, or one JSON object per line
+///
+/// `description` is the name, `project` (or else the first of `tags`) the tag, `due` the
+/// deadline, `status` the completed flag (completed and deleted tasks are completed),
+/// `entry` and `end` the timestamps and `uuid` the UID. Priorities H, M and L become A, B
+/// and C. Every other attribute, and `tags` and `status` where they say more than the
+/// task, is kept in extras.
+pub fn import(content: &str, options: &ImportOptions) -> Result<Vec<ImportedRow>> {
+    let invalid =
+        |e: serde_json::Error| TaskError::validation(format!("Invalid Taskwarrior JSON: {}", e));
+    let values: Vec<Value> = if content.trim_start().starts_with('[') {
+        serde_json::from_str(content).map_err(invalid)?
+    } else {
+        content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(serde_json::from_str)
+            .collect::<Result<_, _>>()
+            .map_err(invalid)?
+    };
+
+    Ok(values
+        .into_iter()
+        .enumerate()
+        .map(|(index, value)| ImportedRow {
+            row: index + 1,
+            task: match value {
+                Value::Object(object) => read_task(object, options),
+                _ => Err("Expected a JSON object".to_string()),
+            },
+        })
+        .collect())
+}
+
+/// Build a task from one Taskwarrior object
+fn read_task(mut object: Map<String, Value>, options: &ImportOptions) -> Result<Task, String> {
+    let mut task = Task::new(0, String::new(), String::new(), None);
+    for key in COMPUTED {
+        object.remove(key);
+    }
+    let mut take_str = |key: &str| -> Result<Option<String>, String> {
+        match object.remove(key) {
+            None => Ok(None),
+            Some(Value::String(s)) => Ok(Some(s)),
+            Some(other) => Err(format!("Invalid {} {} (expected a string)", key, other)),
+        }
+    };
+
+    task.name = take_str("description")?.unwrap_or_default();
+    task.uid = take_str("uuid")?;
+    let project = take_str("project")?;
+    let due = take_str("due")?;
+    let entry = take_str("entry")?;
+    let end = take_str("end")?;
+
+    task.deadline = due
+        .map(|d| parse_date(&d))
+        .transpose()?
+        .map(|t| t.date_naive());
+    task.created_at = entry.map(|e| parse_date(&e)).transpose()?;
+
+    if let Some(status) = object.get("status").and_then(Value::as_str) {
+        task.completed =
+            status_completed(status).ok_or_else(|| format!("Invalid status \"{}\"", status))?;
+        if matches!(status, "pending" | "completed") {
+            object.remove("status");
+        }
+    }
+    match end {
+        Some(end) if task.completed => task.completed_at = Some(parse_date(&end)?),
+        Some(end) => {
+            object.insert("end".to_string(), end.into());
+        }
+        None => {}
+    }
+
+    if let Some(priority) = object.get("priority").and_then(Value::as_str) {
+        if let Some((_, letter)) = PRIORITIES.iter().find(|(name, _)| *name == priority) {
+            task.priority = Some(*letter);
+            object.remove("priority");
+        }
+    }
+
+    let first_tag = object
+        .get("tags")
+        .and_then(Value::as_array)
+        .and_then(|tags| tags.first())
+        .and_then(Value::as_str)
+        .map(str::to_string);
+    task.tag = project
+        .or(first_tag)
+        .or_else(|| options.default_tag.map(str::to_string))
+        .unwrap_or_default();
+
+    task.extras = object.into_iter().collect();
+    Ok(task)
+}
+
+/// Whether a Taskwarrior status counts as completed, or None for an unknown status
+fn status_completed(status: &str) -> Option<bool> {
+    match status {
+        "pending" | "waiting" | "recurring" => Some(false),
+        "completed" | "deleted" => Some(true),
+        _ => None,
+    }
+}
+
+/// Parse a Taskwarrior date such as "20260301T230000Z", or an RFC 3339 timestamp
+fn parse_date(value: &str) -> Result<DateTime<Local>, String> {
+    NaiveDateTime::parse_from_str(value, DATE_FORMAT)
+        .map(|t| t.and_utc().with_timezone(&Local))
+        .or_else(|_| DateTime::parse_from_rfc3339(value).map(|t| t.with_timezone(&Local)))
+        .map_err(|_| format!("Invalid date \"{}\" (expected YYYYMMDDTHHMMSSZ)", value))
+}
+
+/// Start of a day in local time, which is how Taskwarrior stores a due date without time
+fn local_midnight(date: NaiveDate) -> DateTime<Local> {
+    date.and_time(NaiveTime::MIN)
+        .and_local_timezone(Local)
+        .earliest()
+        .unwrap_or_else(Local::now)
+}
+
+/// Whether a UID has the 8-4-4-4-12 hex digit layout of a UUID
+fn is_uuid(uid: &str) -> bool {
+    let groups: Vec<&str> = uid.split('-').collect();
+    groups.iter().map(|g| g.len()).eq([8, 4, 4, 4, 12])
+        && groups
+            .iter()
+            .all(|g| g.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const EXPORT: &str = r#"[
+{"id":1,"description":"Lab report","due":"20260301T230000Z","entry":"20260201T100000Z","modified":"20260202T100000Z","priority":"H","project":"Physics","status":"pending","tags":["lab","graded"],"uuid":"6a1b2c3d-4e5f-4a7b-8c9d-0e1f2a3b4c5d","urgency":8.2,"annotations":[{"entry":"20260202T100000Z","description":"bring goggles"}]},
+{"id":0,"description":"Old essay","end":"20260210T120000Z","entry":"20260101T100000Z","status":"deleted","tags":["writing"],"uuid":"7b2c3d4e-5f6a-4b8c-9d0e-1f2a3b4c5d6e","urgency":0}
+]"#;
+
+    #[test]
+    fn test_import_maps_and_keeps_extras() {
+        let rows = import(EXPORT, &ImportOptions::default()).unwrap();
+        let lab = rows[0].task.as_ref().unwrap();
+        assert_eq!(
+            (lab.name.as_str(), lab.tag.as_str()),
+            ("Lab report", "Physics")
+        );
+        assert_eq!(lab.priority, Some('A'));
+        assert_eq!(
+            lab.uid.as_deref(),
+            Some("6a1b2c3d-4e5f-4a7b-8c9d-0e1f2a3b4c5d")
+        );
+        assert!(!lab.completed);
+        assert_eq!(
+            lab.extras.keys().collect::<Vec<_>>(),
+            ["annotations", "modified", "tags"]
+        );
+        assert_eq!(lab.extras["tags"], json!(["lab", "graded"]));
+
+        let essay = rows[1].task.as_ref().unwrap();
+        assert_eq!(essay.tag, "writing");
+        assert!(essay.completed && essay.completed_at.is_some());
+        assert_eq!(essay.extras["status"], "deleted");
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let rows = import(EXPORT, &ImportOptions::default()).unwrap();
+        let tasks: Vec<Task> = rows.into_iter().map(|r| r.task.unwrap()).collect();
+        let exported: Vec<Value> = serde_json::from_str(&export(&tasks).unwrap()).unwrap();
+        let mut original: Vec<Value> = serde_json::from_str(EXPORT).unwrap();
+        for object in &mut original {
+            let object = object.as_object_mut().unwrap();
+            object.remove("id");
+            object.remove("urgency");
+            // Due dates are written at local midnight
+            object.remove("due");
+        }
+        let mut exported = exported;
+        exported[0].as_object_mut().unwrap().remove("due");
+        assert_eq!(exported, original);
+    }
+
+    #[test]
+    fn test_export_new_task() {
+        let mut task = Task::new(
+            3,
+            "Quiz".to_string(),
+            "Math".to_string(),
+            NaiveDate::from_ymd_opt(2026, 3, 5),
+        );
+        task.uid = Some("hw-1@lms".to_string());
+        task.priority = Some('D');
+        let object = to_json(&task);
+        assert_eq!(object["project"], "Math");
+        assert_eq!(object["status"], "pending");
+        assert!(!object.contains_key("uuid") && !object.contains_key("priority"));
+        let due = parse_date(object["due"].as_str().unwrap()).unwrap();
+        assert_eq!(
+            due.date_naive(),
+            NaiveDate::from_ymd_opt(2026, 3, 5).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_import_errors() {
+        assert!(import("not json", &ImportOptions::default()).is_err());
+        let rows = import(
+            "{\"description\":\"Quiz\",\"due\":\"soon\"}\n42\n",
+            &ImportOptions::default(),
+        )
+        .unwrap();
+        assert!(rows[0]
+            .task
+            .as_ref()
+            .unwrap_err()
+            .starts_with("Invalid date"));
+        assert_eq!(rows[1].task, Err("Expected a JSON object".to_string()));
+    }
+
+    #[test]
+    fn test_is_uuid() {
+        assert!(is_uuid("6a1b2c3d-4e5f-4a7b-8c9d-0e1f2a3b4c5d"));
+        assert!(!is_uuid("hw-1@lms"));
+        assert!(!is_uuid("6a1b2c3d-4e5f-4a7b-8c9d-0e1f2a3b4c5"));
+    }
+}
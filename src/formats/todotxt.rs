@@ -0,0 +1,335 @@
+use super::{parse_priority, ExportOptions, ImportOptions, ImportedRow};
+use crate::models::Task;
+use chrono::{DateTime, Local, NaiveDate};
+
+/// Date format of todo.txt
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// Write one todo.txt line per task:
+/// `[x COMPLETED] [(A)] [CREATED] name +tag [due:DATE] [pri:A] [uid:UID]`.
+/// Completed tasks keep their priority as `pri:`, since todo.txt only allows `(A)` on open
+/// tasks. The tag is written as a project, with whitespace and `%` percent-encoded.
+///
+/// todo.txt cannot say that a date is missing, so dates are also written where leaving them
+/// out would change how the line reads: before a name starting with `x`, a priority or a
+/// date, and as the completion date in front of a creation date. A date the task lacks is
+/// then the day of the export.
+pub fn export(tasks: &[Task], options: &ExportOptions) -> String {
+    tasks
+        .iter()
+        .map(|task| format_line(task, options.now) + "\n")
+        .collect()
+}
+
+/// Format one task as a todo.txt line
+fn format_line(task: &Task, now: DateTime<Local>) -> String {
+    let mut parts = Vec::new();
+    let day = |t: DateTime<Local>| t.format(DATE_FORMAT).to_string();
+    let first_word = task.name.split_whitespace().next().unwrap_or_default();
+    let starts_with_date = NaiveDate::parse_from_str(first_word, DATE_FORMAT).is_ok();
+    if task.completed {
+        parts.push("x".to_string());
+        // A creation date is only allowed after a completion date
+        let needs_dates = starts_with_date || task.created_at.is_some();
+        if let Some(completed) = task.completed_at.or(needs_dates.then_some(now)) {
+            parts.push(day(completed));
+            let created = task.created_at.or(starts_with_date.then_some(now));
+            parts.extend(created.map(day));
+        }
+    } else {
+        parts.extend(task.priority.map(|p| format!("({})", p)));
+        let ambiguous =
+            starts_with_date || first_word == "x" || priority_letter(first_word).is_some();
+        parts.extend(task.created_at.or(ambiguous.then_some(now)).map(day));
+    }
+    parts.push(task.name.clone());
+    parts.push(format!("+{}", encode_tag(&task.tag)));
+    if let Some(deadline) = task.deadline {
+        parts.push(format!("due:{}", deadline.format(DATE_FORMAT)));
+    }
+    if task.completed {
+        parts.extend(task.priority.map(|p| format!("pri:{}", p)));
+    }
+    if let Some(uid) = &task.uid {
+        parts.push(format!("uid:{}", uid));
+    }
+    parts.join(" ")
+}
+
+/// Read todo.txt lines as tasks, skipping blank lines. The tag is the last `+project`, or
+/// the last `@context` without one, and is removed from the name; other projects and
+/// contexts stay in the name. `due:`, `pri:` and `uid:` are only read from the key:value
+/// tokens that end the line. Dates give timestamps at local midnight.
+pub fn import(content: &str, options: &ImportOptions) -> Vec<ImportedRow> {
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .enumerate()
+        .map(|(index, line)| ImportedRow {
+            row: index + 1,
+            task: read_task(line, options),
+        })
+        .collect()
+}
+
+/// Build a task from one line
+fn read_task(line: &str, options: &ImportOptions) -> Result<Task, String> {
+    let mut task = Task::new(0, String::new(), String::new(), None);
+    let tokens: Vec<&str> = line.split_whitespace().collect();
+    let mut rest = tokens.as_slice();
+
+    if let Some((&"x", tail)) = rest.split_first() {
+        task.completed = true;
+        rest = tail;
+    }
+    if let Some((first, tail)) = rest.split_first() {
+        if let Some(priority) = priority_letter(first).filter(|_| !task.completed) {
+            task.priority = Some(parse_priority(priority)?);
+            rest = tail;
+        }
+    }
+    let mut dates = Vec::new();
+    while let Some(date) = rest
+        .first()
+        .filter(|_| dates.len() < if task.completed { 2 } else { 1 })
+        .and_then(|t| NaiveDate::parse_from_str(t, DATE_FORMAT).ok())
+    {
+        dates.push(date);
+        rest = &rest[1..];
+    }
+    let mut dates = dates.into_iter().map(midnight);
+    if task.completed {
+        task.completed_at = dates.next();
+    }
+    task.created_at = dates.next();
+
+    // Only the trailing run of key:value tokens is metadata, so that a name word such as
+    // `due:dates`, which export writes before the tag, stays part of the name
+    let metadata_start = rest
+        .iter()
+        .rposition(|t| !is_key_value(t))
+        .map_or(0, |i| i + 1);
+    let (name_words, metadata) = rest.split_at(metadata_start);
+    let mut words = name_words.to_vec();
+    for &token in metadata {
+        match token.split_once(':') {
+            Some(("due", value)) => {
+                task.deadline =
+                    Some(NaiveDate::parse_from_str(value, DATE_FORMAT).map_err(|_| {
+                        format!("Invalid due date \"{}\" (expected YYYY-MM-DD)", value)
+                    })?)
+            }
+            Some(("pri", value)) => task.priority = Some(parse_priority(value)?),
+            Some(("uid", value)) if !value.is_empty() => task.uid = Some(value.to_string()),
+            _ => words.push(token),
+        }
+    }
+
+    let tag_index = ['+', '@'].into_iter().find_map(|prefix| {
+        words
+            .iter()
+            .rposition(|w| w.len() > 1 && w.starts_with(prefix))
+    });
+    task.tag = match tag_index {
+        Some(index) => decode_tag(&words.remove(index)[1..]),
+        None => options.default_tag.unwrap_or_default().to_string(),
+    };
+    task.name = words.join(" ");
+    Ok(task)
+}
+
+/// Whether a token has the todo.txt `key:value` form; projects and contexts never do
+fn is_key_value(token: &str) -> bool {
+    !token.starts_with(['+', '@'])
+        && token
+            .split_once(':')
+            .is_some_and(|(key, value)| !key.is_empty() && !value.is_empty())
+}
+
+/// The letter of a `(A)` priority marker; any single character counts, so that an invalid
+/// one is reported instead of becoming part of the name
+fn priority_letter(token: &str) -> Option<&str> {
+    token
+        .strip_prefix('(')
+        .and_then(|p| p.strip_suffix(')'))
+        .filter(|l| l.chars().count() == 1)
+}
+
+/// Start of a day in local time
+fn midnight(date: NaiveDate) -> DateTime<Local> {
+    date.and_time(chrono::NaiveTime::MIN)
+        .and_local_timezone(Local)
+        .earliest()
+        .unwrap_or_else(Local::now)
+}
+
+/// Percent-encode the characters a project cannot contain
+fn encode_tag(tag: &str) -> String {
+    tag.chars()
+        .map(|c| match c {
+            '%' => "%25".to_string(),
+            c if c.is_whitespace() => c
+                .to_string()
+                .bytes()
+                .map(|b| format!("%{:02X}", b))
+                .collect(),
+            c => c.to_string(),
+        })
+        .collect()
+}
+
+/// Undo `encode_tag`; anything that is not a valid escape is kept as written
+fn decode_tag(tag: &str) -> String {
+    let bytes = tag.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escape = (bytes[i] == b'%')
+            .then(|| tag.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escape {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8(decoded).unwrap_or_else(|_| tag.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_export_lines() {
+        let mut open = Task::new(
+            1,
+            "Call lab +safety".to_string(),
+            "Physics 101".to_string(),
+            Some(date(3, 1)),
+        );
+        open.priority = Some('A');
+        open.created_at = Some(midnight(date(2, 1)));
+        let mut done = Task::new(2, "Essay".to_string(), "Writing".to_string(), None);
+        done.priority = Some('C');
+        done.uid = Some("e1@silver-tasks".to_string());
+        done.complete(midnight(date(2, 20)));
+
+        assert_eq!(
+            export(&[open, done], &ExportOptions::sample()),
+            "(A) 2026-02-01 Call lab +safety +Physics%20101 due:2026-03-01\n\
+             x 2026-02-20 Essay +Writing pri:C uid:e1@silver-tasks\n"
+        );
+    }
+
+    #[test]
+    fn test_import_lines() {
+        let content = "(B) 2026-02-01 Call Mom @phone due:2026-03-05\n\
+                       \n\
+                       x 2026-02-20 2026-02-10 Essay +Writing +draft pri:a\n\
+                       Quiz due:soon\n";
+        let rows = import(content, &ImportOptions::default());
+        let call = rows[0].task.as_ref().unwrap();
+        assert_eq!(
+            (call.name.as_str(), call.tag.as_str()),
+            ("Call Mom", "phone")
+        );
+        assert_eq!(call.priority, Some('B'));
+        assert_eq!(call.deadline, Some(date(3, 5)));
+        assert_eq!(call.created_at, Some(midnight(date(2, 1))));
+
+        let essay = rows[1].task.as_ref().unwrap();
+        assert_eq!(
+            (essay.name.as_str(), essay.tag.as_str()),
+            ("Essay +Writing", "draft")
+        );
+        assert!(essay.completed);
+        assert_eq!(essay.completed_at, Some(midnight(date(2, 20))));
+        assert_eq!(essay.created_at, Some(midnight(date(2, 10))));
+        assert_eq!(essay.priority, Some('A'));
+
+        assert_eq!(rows[2].row, 3);
+        assert!(rows[2]
+            .task
+            .as_ref()
+            .unwrap_err()
+            .starts_with("Invalid due date"));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut task = Task::new(
+            0,
+            "Read ch. 3 @library".to_string(),
+            "Lit 100%".to_string(),
+            Some(date(3, 1)),
+        );
+        task.created_at = Some(midnight(date(2, 1)));
+        task.priority = Some('B');
+        task.uid = Some("r3@silver-tasks".to_string());
+        let mut done = task.clone();
+        done.complete(midnight(date(2, 15)));
+
+        for task in [task, done] {
+            let rows = import(
+                &export(std::slice::from_ref(&task), &ExportOptions::sample()),
+                &ImportOptions::default(),
+            );
+            assert_eq!(rows[0].task, Ok(task));
+        }
+    }
+
+    #[test]
+    fn test_round_trip_ambiguous_names() {
+        let options = ExportOptions::sample();
+        let export_day = Some(midnight(options.now.date_naive()));
+        let read_back = |task: &Task| {
+            let rows = import(
+                &export(std::slice::from_ref(task), &options),
+                &ImportOptions::default(),
+            );
+            rows.into_iter().next().unwrap().task.unwrap()
+        };
+
+        for name in [
+            "x marks the spot",
+            "2026-01-01 retrospective",
+            "(B) not a priority",
+        ] {
+            let task = Task::new(0, name.to_string(), "Notes".to_string(), None);
+            let mut expected = task.clone();
+            expected.created_at = export_day;
+            assert_eq!(read_back(&task), expected, "{}", name);
+
+            let mut done = task.clone();
+            done.completed = true;
+            let read = read_back(&done);
+            assert_eq!((read.name.as_str(), read.completed), (name, true));
+        }
+
+        // Words that look like metadata are only metadata at the end of the line
+        for name in ["Check due:dates", "Read pri:x notes", "Ask about uid:42"] {
+            let mut task = Task::new(0, name.to_string(), "Lab:1".to_string(), Some(date(3, 1)));
+            task.priority = Some('B');
+            assert_eq!(read_back(&task), task, "{}", name);
+        }
+
+        // Completed without a completion time, which todo.txt needs before a creation date
+        let mut done = Task::new(0, "Essay".to_string(), "Writing".to_string(), None);
+        done.completed = true;
+        done.created_at = Some(midnight(date(2, 1)));
+        let read = read_back(&done);
+        assert_eq!(read.created_at, done.created_at);
+        assert_eq!(read.completed_at, export_day);
+    }
+}
@@ -1,14 +1,49 @@
 mod commands;
+mod config;
+mod error;
+mod filter;
+mod formats;
 mod models;
+mod output;
 mod storage;
+mod template;
+mod theme;
+mod ui;
 
 use clap::{Parser, Subcommand};
+use commands::load::LoadPeriod;
+use commands::GlobalOptions;
+use config::{Settings, SortOrder};
+use formats::{ExchangeFormat, IcsTagSource};
+use output::OutputFormat;
+use std::path::PathBuf;
+use ui::ColorChoice;
 
 #[derive(Parser)]
 #[command(name = "silver-tasks")]
 #[command(about = "Personal task manager for university students and productivity", long_about = None)]
 #[command(version = "0.1.0")]
 struct Cli {
+    /// Print what would change without saving anything
+    #[arg(long, global = true)]
+    dry_run: bool,
+
+    /// Skip confirmation prompts (for scripts)
+    #[arg(short = 'y', long, global = true)]
+    yes: bool,
+
+    /// Output format for results and errors
+    #[arg(long, global = true, value_enum, default_value_t = OutputFormat::Text)]
+    output: OutputFormat,
+
+    /// When to use colors and strikethrough (honors NO_COLOR in auto mode) [default: auto]
+    #[arg(long, global = true, value_enum)]
+    color: Option<ColorChoice>,
+
+    /// Directory holding the task files (overrides the data_dir setting)
+    #[arg(long, global = true)]
+    data_dir: Option<PathBuf>,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -19,45 +54,363 @@ enum Commands {
     Add {
         /// Task name/description (1-200 characters)
         name: String,
-        /// Tag for categorization
-        tag: String,
-        /// Optional deadline (DD.MM.YYYY, YYYY-MM-DD, or DD/MM/YYYY)
+        /// Tag for categorization (defaults to the default_tag setting)
+        tag: Option<String>,
+        /// Optional deadline (DD.MM.YYYY, YYYY-MM-DD, DD/MM/YYYY, or the dates.input setting)
         deadline: Option<String>,
     },
 
     /// List tasks grouped by tag and sorted by deadline
-    List {},
+    List {
+        /// Wrap long names onto extra lines instead of truncating them to the terminal width
+        #[arg(long)]
+        wrap: bool,
+        /// Order of tasks within each tag, or of table rows (overrides the sort setting)
+        #[arg(long, value_enum)]
+        sort: Option<SortOrder>,
+        /// Print each task with a named format from the config or a template,
+        /// e.g. "{id} {name:<30} {deadline:%a %d %b}" (overrides the list_format setting)
+        #[arg(long, conflicts_with = "table")]
+        format: Option<String>,
+        /// Show tasks as a bordered table sorted across all tags
+        #[arg(long)]
+        table: bool,
+    },
+
+    /// Show active tasks grouped by when they are due instead of by tag
+    Agenda {
+        /// Only show tasks due within this many days (plus overdue and undated tasks)
+        #[arg(long)]
+        days: Option<u32>,
+    },
+
+    /// Draw a calendar grid with active tasks on their deadlines
+    Calendar {
+        /// Month to show, as YYYY-MM (defaults to the current month)
+        #[arg(long, conflicts_with = "week")]
+        month: Option<String>,
+        /// Show only the current week, with more room per day
+        #[arg(long)]
+        week: bool,
+    },
+
+    /// Show how many deadlines fall on each upcoming day or week
+    Load {
+        /// Histogram rows: one per day or per week
+        #[arg(long, value_enum, default_value_t)]
+        by: LoadPeriod,
+        /// Number of days or weeks to show (default: 14 days or 8 weeks)
+        #[arg(long)]
+        span: Option<u32>,
+    },
+
+    /// Show completion statistics: per week, on-time rate per tag, lead time and streaks
+    Stats {
+        /// First day to include (default: Monday seven weeks ago)
+        #[arg(long)]
+        since: Option<String>,
+        /// Last day to include (default: today)
+        #[arg(long)]
+        until: Option<String>,
+    },
 
-    /// Mark task(s) as completed (soft delete). Accepts one or more task IDs
+    /// Plot open tasks over time against a straight line to zero
+    Burndown {
+        /// Only count tasks with this tag
+        #[arg(long)]
+        tag: Option<String>,
+        /// First day (default: when the earliest task was created)
+        #[arg(long)]
+        from: Option<String>,
+        /// Last day (default: the latest deadline, or today)
+        #[arg(long)]
+        to: Option<String>,
+        /// Also write the chart as an SVG file
+        #[arg(long, value_name = "FILE")]
+        svg: Option<PathBuf>,
+    },
+
+    /// Mark task(s) as completed (soft delete). Accepts task IDs, ranges and selectors
     Remove {
-        /// Task IDs to mark completed (space-separated)
+        /// Task IDs or ranges to mark completed (e.g. 3-7 10 12-)
         ids: Vec<String>,
+        /// Mark all active tasks with this tag
+        #[arg(long)]
+        tag: Option<String>,
+        /// Mark all active tasks whose deadline has passed
+        #[arg(long)]
+        overdue: bool,
+    },
+
+    /// Move all completed tasks to the archive
+    Clear {
+        /// Permanently delete completed tasks instead of archiving them
+        #[arg(long)]
+        purge: bool,
+    },
+
+    /// Browse, search and restore archived tasks
+    Archive {
+        #[command(subcommand)]
+        command: ArchiveCommands,
+    },
+
+    /// Browse and restore backups of the task list
+    Backup {
+        #[command(subcommand)]
+        command: BackupCommands,
+    },
+
+    /// Write tasks to a file in another format
+    Export {
+        /// File format
+        #[arg(long, value_enum)]
+        format: ExchangeFormat,
+        /// Only export matching tasks: tag=, status=, name=, due_before= or due_after=
+        /// (repeatable, all must match)
+        #[arg(long, value_name = "KEY=VALUE")]
+        filter: Vec<String>,
+        /// Write to this file instead of stdout
+        #[arg(short = 'o', long = "out", value_name = "FILE")]
+        out: Option<PathBuf>,
+        /// Write all-day events on the deadline instead of to-dos (ics only)
+        #[arg(long)]
+        events: bool,
+    },
+
+    /// Add tasks from a file in another format
+    Import {
+        /// File format
+        #[arg(long, value_enum)]
+        format: ExchangeFormat,
+        /// File to read
+        file: PathBuf,
+        /// Read a column as a task field, e.g. Course=tag (repeatable)
+        #[arg(long = "map", value_name = "COLUMN=FIELD")]
+        map: Vec<String>,
+        /// Take the tag of ics entries from their categories (default) or the calendar name
+        #[arg(long, value_enum, ignore_case = true)]
+        tag_from: Option<IcsTagSource>,
+    },
+
+    /// Write a report to share or print
+    Report {
+        #[command(subcommand)]
+        command: ReportCommands,
+    },
+
+    /// Show and change settings in the config file
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommands,
+    },
+}
+
+#[derive(Subcommand)]
+enum ArchiveCommands {
+    /// List all archived tasks
+    List {},
+
+    /// Search archived tasks by name or tag (case-insensitive)
+    Search {
+        /// Text to look for in task names and tags
+        query: String,
     },
 
-    /// Permanently delete all completed tasks
-    Clear {},
+    /// Move archived task(s) back into the task list. Accepts one or more archive IDs
+    Restore {
+        /// Archive IDs to restore (space-separated)
+        ids: Vec<String>,
+        /// Mark restored tasks as active again instead of completed
+        #[arg(long)]
+        reopen: bool,
+    },
 }
 
-fn main() -> anyhow::Result<()> {
+#[derive(Subcommand)]
+enum BackupCommands {
+    /// List backups of the task list, oldest first
+    List {},
+
+    /// Replace the task list with a backup, after showing what changes
+    Restore {
+        /// Backup stamp from `backup list`, or a unique prefix of it
+        stamp: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum ReportCommands {
+    /// Self-contained HTML page with tasks by tag, a deadline timeline and completion stats
+    Html {
+        /// Write to this file instead of stdout
+        #[arg(short = 'o', long = "out", value_name = "FILE")]
+        out: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum ConfigCommands {
+    /// Print the effective value of a setting
+    Get {
+        /// Setting key, e.g. sort or dates.output
+        key: String,
+    },
+
+    /// Set a value in the config file
+    Set {
+        /// Setting key, e.g. sort or theme.tags.Calculus
+        key: String,
+        /// New value
+        value: String,
+    },
+
+    /// List all settings with their values and where they come from
+    List {},
+}
+
+fn main() {
     let cli = Cli::parse();
 
-    match cli.command {
+    let mut opts = GlobalOptions {
+        dry_run: cli.dry_run,
+        assume_yes: cli.yes,
+        output: cli.output,
+        color: cli.color.unwrap_or_default(),
+    };
+
+    let result = resolve_settings(&cli).and_then(|settings| {
+        opts.color = settings.color;
+        run(cli.command, &opts, settings)
+    });
+
+    if let Err(err) = result {
+        output::print_error(opts.output, &err);
+        std::process::exit(error::exit_code(&err));
+    }
+}
+
+/// Resolve settings: built-in defaults, then the config file, then environment variables,
+/// then CLI flags. `config set` skips the config file so that a broken file can still be fixed.
+fn resolve_settings(cli: &Cli) -> anyhow::Result<Settings> {
+    let mut settings = match cli.command {
+        Commands::Config {
+            command: ConfigCommands::Set { .. },
+        } => Settings::default(),
+        _ => Settings::load()?,
+    };
+
+    if let Some(color) = cli.color {
+        settings.color = color;
+    }
+    if let Some(dir) = &cli.data_dir {
+        settings.data_dir = Some(dir.clone());
+    }
+    if let Some(dir) = &settings.data_dir {
+        storage::set_data_dir(dir.clone())?;
+    }
+    storage::set_backup_retention(settings.backup.clone());
+
+    Ok(settings)
+}
+
+/// Dispatch a parsed subcommand to its handler
+fn run(command: Commands, opts: &GlobalOptions, mut settings: Settings) -> anyhow::Result<()> {
+    match command {
         Commands::Add {
             name,
             tag,
             deadline,
         } => {
-            commands::add::add_task(name, tag, deadline)?;
+            commands::add::add_task(name, tag, deadline, &settings, opts)?;
         }
-        Commands::List {} => {
-            commands::list::list_tasks()?;
+        Commands::List {
+            wrap,
+            sort,
+            format,
+            table,
+        } => {
+            if let Some(sort) = sort {
+                settings.sort = sort;
+            }
+            let template = match format {
+                Some(format) => Some(settings.template(&format)?),
+                None => settings.list_format.clone(),
+            };
+            commands::list::list_tasks(opts, &settings, template.as_ref(), table, wrap)?;
         }
-        Commands::Remove { ids } => {
-            commands::remove::remove_tasks(ids)?;
+        Commands::Agenda { days } => {
+            commands::agenda::show_agenda(days, &settings, opts)?;
         }
-        Commands::Clear {} => {
-            commands::clear::clear_tasks()?;
+        Commands::Calendar { month, week } => {
+            commands::calendar::show_calendar(month, week, &settings, opts)?;
+        }
+        Commands::Load { by, span } => {
+            commands::load::show_load(by, span, &settings, opts)?;
+        }
+        Commands::Stats { since, until } => {
+            commands::stats::show_stats(since, until, &settings, opts)?;
+        }
+        Commands::Burndown { tag, from, to, svg } => {
+            commands::burndown::show_burndown(tag, from, to, svg, &settings, opts)?;
+        }
+        Commands::Remove { ids, tag, overdue } => {
+            commands::remove::remove_tasks(ids, tag, overdue, opts)?;
+        }
+        Commands::Clear { purge } => {
+            commands::clear::clear_tasks(purge, opts)?;
+        }
+        Commands::Export {
+            format,
+            filter,
+            out,
+            events,
+        } => {
+            commands::export::export_tasks(format, filter, out, events, &settings, opts)?;
+        }
+        Commands::Import {
+            format,
+            file,
+            map,
+            tag_from,
+        } => {
+            commands::import::import_tasks(format, file, map, tag_from, &settings, opts)?;
         }
+        Commands::Archive { command } => match command {
+            ArchiveCommands::List {} => {
+                commands::archive::list_archive(&settings.date_output_format, opts)?;
+            }
+            ArchiveCommands::Search { query } => {
+                commands::archive::search_archive(query, &settings.date_output_format, opts)?;
+            }
+            ArchiveCommands::Restore { ids, reopen } => {
+                commands::archive::restore_tasks(ids, reopen, opts)?;
+            }
+        },
+        Commands::Backup { command } => match command {
+            BackupCommands::List {} => {
+                commands::backup::list_backups(&settings.date_output_format, opts)?;
+            }
+            BackupCommands::Restore { stamp } => {
+                commands::backup::restore_backup(stamp, &settings.date_output_format, opts)?;
+            }
+        },
+        Commands::Report { command } => match command {
+            ReportCommands::Html { out } => {
+                commands::report::report_html(out, &settings, opts)?;
+            }
+        },
+        Commands::Config { command } => match command {
+            ConfigCommands::Get { key } => {
+                commands::config::get_value(key, &settings, opts)?;
+            }
+            ConfigCommands::Set { key, value } => {
+                commands::config::set_value(key, value, opts)?;
+            }
+            ConfigCommands::List {} => {
+                commands::config::list_values(&settings, opts)?;
+            }
+        },
     }
 
     Ok(())
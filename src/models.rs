@@ -1,5 +1,8 @@
-use chrono::NaiveDate;
+use crate::error::TaskError;
+use chrono::{DateTime, Local, NaiveDate};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
 
 /// Represents a task in the system
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -15,6 +18,34 @@ pub struct Task {
     /// Completed flag - true when task is marked completed. Default false when absent in storage.
     #[serde(default)]
     pub completed: bool,
+    /// When the task was created; absent for tasks created before this was recorded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<DateTime<Local>>,
+    /// When the task was marked completed; absent while active or when not recorded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completed_at: Option<DateTime<Local>>,
+    /// Stable identifier for calendar sync, assigned on first export or taken from an
+    /// imported calendar entry
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uid: Option<String>,
+    /// Optional priority letter, A (highest) to Z, as in todo.txt
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<char>,
+    /// Attributes from other tools that have no task field, kept so exports back to the
+    /// same tool do not lose them
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub extras: BTreeMap<String, Value>,
+}
+
+/// A completed task moved out of the active list by `clear`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ArchivedTask {
+    /// Archive identifier (independent of task IDs, recycled)
+    pub id: u32,
+    /// Date the task was moved to the archive
+    pub archived_on: NaiveDate,
+    /// The task as it was when archived
+    pub task: Task,
 }
 
 /// Represents a tag for categorizing tasks
@@ -33,24 +64,61 @@ impl Task {
             tag,
             deadline,
             completed: false,
+            created_at: None,
+            completed_at: None,
+            uid: None,
+            priority: None,
+            extras: BTreeMap::new(),
         }
     }
 
+    /// Mark the task completed at `at`. An already completed task keeps its timestamp.
+    pub fn complete(&mut self, at: DateTime<Local>) {
+        if !self.completed {
+            self.completed = true;
+            self.completed_at = Some(at);
+        }
+    }
+
+    /// Make a completed task active again
+    pub fn reopen(&mut self) {
+        self.completed = false;
+        self.completed_at = None;
+    }
+
     /// Validates task data
-    pub fn validate(&self) -> anyhow::Result<()> {
+    pub fn validate(&self) -> Result<(), TaskError> {
         if self.name.is_empty() {
-            anyhow::bail!("Task name cannot be empty");
+            return Err(TaskError::validation("Task name cannot be empty"));
         }
         if self.name.len() > 200 {
-            anyhow::bail!("Task name too long (max 200 characters)");
+            return Err(TaskError::validation(
+                "Task name too long (max 200 characters)",
+            ));
         }
         if self.tag.is_empty() {
-            anyhow::bail!("Tag name cannot be empty");
+            return Err(TaskError::validation("Tag name cannot be empty"));
+        }
+        if self.priority.is_some_and(|p| !p.is_ascii_uppercase()) {
+            return Err(TaskError::validation(
+                "Priority must be a letter from A to Z",
+            ));
         }
         Ok(())
     }
 }
 
+impl ArchivedTask {
+    /// Wraps a task for storage in the archive
+    pub fn new(id: u32, archived_on: NaiveDate, task: Task) -> Self {
+        Self {
+            id,
+            archived_on,
+            task,
+        }
+    }
+}
+
 impl Tag {
     /// Creates a new tag
     pub fn new(name: String) -> Self {
@@ -58,9 +126,9 @@ impl Tag {
     }
 
     /// Validates tag data
-    pub fn validate(&self) -> anyhow::Result<()> {
+    pub fn validate(&self) -> Result<(), TaskError> {
         if self.name.is_empty() {
-            anyhow::bail!("Tag name cannot be empty");
+            return Err(TaskError::validation("Tag name cannot be empty"));
         }
         Ok(())
     }
@@ -0,0 +1,105 @@
+use crate::error::{self, TaskError};
+use clap::ValueEnum;
+use serde::Serialize;
+
+/// How command results and errors are written
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable text
+    #[default]
+    Text,
+    /// A single pretty-printed JSON document per command
+    Json,
+    /// Newline-delimited JSON: one compact object per line
+    Ndjson,
+}
+
+impl OutputFormat {
+    /// Whether results should be written as JSON instead of text
+    pub fn is_structured(self) -> bool {
+        self != OutputFormat::Text
+    }
+}
+
+/// Print a command result as a single JSON document (or line, for ndjson).
+/// Does nothing for text output; commands print their own text.
+pub fn print_result<T: Serialize>(format: OutputFormat, value: &T) {
+    match format {
+        OutputFormat::Text => {}
+        OutputFormat::Json => println!("{}", to_json(value, true)),
+        OutputFormat::Ndjson => println!("{}", to_json(value, false)),
+    }
+}
+
+/// Print a list of records. JSON wraps them in `{"command": ..., "<key>": [...]}`,
+/// ndjson prints one compact record per line.
+pub fn print_records<T: Serialize>(format: OutputFormat, command: &str, key: &str, records: &[T]) {
+    match format {
+        OutputFormat::Text => {}
+        OutputFormat::Json => {
+            let mut map = serde_json::Map::new();
+            map.insert("command".to_string(), command.into());
+            map.insert(
+                key.to_string(),
+                serde_json::to_value(records).unwrap_or_default(),
+            );
+            println!("{}", to_json(&map, true));
+        }
+        OutputFormat::Ndjson => {
+            for record in records {
+                println!("{}", to_json(record, false));
+            }
+        }
+    }
+}
+
+/// Print an error to stderr in the requested format
+pub fn print_error(format: OutputFormat, err: &anyhow::Error) {
+    if !format.is_structured() {
+        // Matches anyhow's default report for errors returned from main
+        eprintln!("Error: {:?}", err);
+        return;
+    }
+
+    let causes: Vec<String> = err.chain().skip(1).map(|c| c.to_string()).collect();
+    let kind = error::find_task_error(err).map_or("other", TaskError::kind);
+    let value = serde_json::json!({
+        "error": {
+            "kind": kind,
+            "exit_code": error::exit_code(err),
+            "message": err.to_string(),
+            "causes": causes,
+        }
+    });
+    eprintln!("{}", to_json(&value, format == OutputFormat::Json));
+}
+
+/// Serialize a value, falling back to `null` if serialization fails
+fn to_json<T: Serialize>(value: &T, pretty: bool) -> String {
+    let result = if pretty {
+        serde_json::to_string_pretty(value)
+    } else {
+        serde_json::to_string(value)
+    };
+    result.unwrap_or_else(|_| "null".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_json_compact_is_single_line() {
+        let value = serde_json::json!({"command": "clear", "removed": 2});
+        let line = to_json(&value, false);
+        assert!(!line.contains('\n'));
+        assert_eq!(line, r#"{"command":"clear","removed":2}"#);
+    }
+
+    #[test]
+    fn test_is_structured() {
+        assert!(!OutputFormat::Text.is_structured());
+        assert!(OutputFormat::Json.is_structured());
+        assert!(OutputFormat::Ndjson.is_structured());
+    }
+}
@@ -1,18 +1,113 @@
-use crate::models::{Tag, Task};
-use anyhow::{Context, Result};
+use crate::config::BackupRetention;
+use crate::error::TaskError;
+use crate::models::{ArchivedTask, Tag, Task};
+use anyhow::Result;
+use chrono::{Local, NaiveDate, NaiveDateTime};
+use fs2::FileExt;
+use std::collections::HashSet;
 use std::fs;
 use std::path::PathBuf;
+use std::sync::OnceLock;
 
-/// Get the path to the tasks file in the user's home directory
+/// Directory set through the `data_dir` setting; the home directory when unset
+static DATA_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Retention set through the `[backup]` settings; the defaults when unset
+static BACKUP_RETENTION: OnceLock<BackupRetention> = OnceLock::new();
+
+/// Name of a backup file without its extension, e.g. 20261019-143012.123. Stamps sort in
+/// the order the backups were made.
+const BACKUP_STAMP: &str = "%Y%m%d-%H%M%S%.3f";
+
+/// Like `anyhow::Context::context`, but marks the failure as a storage error
+trait StorageContext<T> {
+    fn storage_context(self, message: &str) -> Result<T>;
+}
+
+impl<T, E> StorageContext<T> for std::result::Result<T, E>
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    fn storage_context(self, message: &str) -> Result<T> {
+        self.map_err(|e| TaskError::storage(message, e).into())
+    }
+}
+
+/// Exclusive lock on the storage files, released when dropped
+pub struct StorageLock {
+    _file: fs::File,
+}
+
+/// Store task files in `dir` instead of the home directory, creating it if needed.
+/// Only the first call has an effect.
+pub fn set_data_dir(dir: PathBuf) -> Result<()> {
+    fs::create_dir_all(&dir).storage_context("Failed to create data directory")?;
+    let _ = DATA_DIR.set(dir);
+    Ok(())
+}
+
+/// Keep backups of the tasks file as set by `retention`. Only the first call has an effect.
+pub fn set_backup_retention(retention: BackupRetention) {
+    let _ = BACKUP_RETENTION.set(retention);
+}
+
+/// Get the directory where all storage files live: the configured data directory,
+/// or the user's home directory
+fn data_dir() -> Result<PathBuf> {
+    if let Some(dir) = DATA_DIR.get() {
+        return Ok(dir.clone());
+    }
+    let home = dirs::home_dir().ok_or_else(|| TaskError::Storage {
+        message: "Could not determine home directory".to_string(),
+        source: None,
+    })?;
+    Ok(home)
+}
+
+/// Get the path to the tasks file in the data directory
 fn tasks_file_path() -> Result<PathBuf> {
-    let home = dirs::home_dir().context("Could not determine home directory")?;
-    Ok(home.join(".tasks.json"))
+    Ok(data_dir()?.join(".tasks.json"))
 }
 
-/// Get the path to the tags file in the user's home directory
+/// Get the path to the tags file in the data directory
 fn tags_file_path() -> Result<PathBuf> {
-    let home = dirs::home_dir().context("Could not determine home directory")?;
-    Ok(home.join(".tasks-tags.json"))
+    Ok(data_dir()?.join(".tasks-tags.json"))
+}
+
+/// Get the path to the archive file in the data directory
+fn archive_file_path() -> Result<PathBuf> {
+    Ok(data_dir()?.join(".tasks-archive.json"))
+}
+
+/// Get the path to the backups directory in the data directory
+fn backup_dir_path() -> Result<PathBuf> {
+    Ok(data_dir()?.join(".tasks-backups"))
+}
+
+/// Get the path to the lock file in the data directory
+fn lock_file_path() -> Result<PathBuf> {
+    Ok(data_dir()?.join(".tasks.lock"))
+}
+
+/// Take the exclusive storage lock for a command that modifies storage.
+/// Fails immediately with `TaskError::LockContention` if another process holds it.
+pub fn lock_storage() -> Result<StorageLock> {
+    let path = lock_file_path()?;
+    let file = fs::OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(&path)
+        .storage_context("Failed to open lock file")?;
+
+    if let Err(e) = file.try_lock_exclusive() {
+        if e.raw_os_error() == fs2::lock_contended_error().raw_os_error() {
+            return Err(TaskError::LockContention.into());
+        }
+        return Err(TaskError::storage("Failed to lock task files", e).into());
+    }
+
+    Ok(StorageLock { _file: file })
 }
 
 /// Load all tasks from storage
@@ -21,20 +116,22 @@ pub fn load_tasks() -> Result<Vec<Task>> {
 
     // Initialize empty file if it doesn't exist
     if !path.exists() {
-        fs::write(&path, "[]").context("Failed to create tasks file")?;
+        fs::write(&path, "[]").storage_context("Failed to create tasks file")?;
         return Ok(Vec::new());
     }
 
-    let content = fs::read_to_string(&path).context("Failed to read tasks file")?;
-    let tasks: Vec<Task> = serde_json::from_str(&content).context("Failed to parse tasks file")?;
+    let content = fs::read_to_string(&path).storage_context("Failed to read tasks file")?;
+    let tasks: Vec<Task> =
+        serde_json::from_str(&content).storage_context("Failed to parse tasks file")?;
     Ok(tasks)
 }
 
-/// Save all tasks to storage
+/// Save all tasks to storage, backing up the previous tasks file first
 pub fn save_tasks(tasks: &[Task]) -> Result<()> {
+    back_up_tasks()?;
     let path = tasks_file_path()?;
-    let json = serde_json::to_string_pretty(tasks)?;
-    fs::write(&path, json).context("Failed to save tasks")?;
+    let json = serde_json::to_string_pretty(tasks).storage_context("Failed to serialize tasks")?;
+    fs::write(&path, json).storage_context("Failed to save tasks")?;
     Ok(())
 }
 
@@ -44,12 +141,13 @@ pub fn load_tags() -> Result<Vec<Tag>> {
 
     // Initialize empty file if it doesn't exist
     if !path.exists() {
-        fs::write(&path, "[]").context("Failed to create tags file")?;
+        fs::write(&path, "[]").storage_context("Failed to create tags file")?;
         return Ok(Vec::new());
     }
 
-    let content = fs::read_to_string(&path).context("Failed to read tags file")?;
-    let tags: Vec<String> = serde_json::from_str(&content).context("Failed to parse tags file")?;
+    let content = fs::read_to_string(&path).storage_context("Failed to read tags file")?;
+    let tags: Vec<String> =
+        serde_json::from_str(&content).storage_context("Failed to parse tags file")?;
     let tags = tags.into_iter().map(Tag::new).collect();
     Ok(tags)
 }
@@ -58,18 +156,162 @@ pub fn load_tags() -> Result<Vec<Tag>> {
 pub fn save_tags(tags: &[Tag]) -> Result<()> {
     let path = tags_file_path()?;
     let names: Vec<String> = tags.iter().map(|t| t.name.clone()).collect();
-    let json = serde_json::to_string_pretty(&names)?;
-    fs::write(&path, json).context("Failed to save tags")?;
+    let json = serde_json::to_string_pretty(&names).storage_context("Failed to serialize tags")?;
+    fs::write(&path, json).storage_context("Failed to save tags")?;
+    Ok(())
+}
+
+/// Load all archived tasks from storage
+pub fn load_archive() -> Result<Vec<ArchivedTask>> {
+    let path = archive_file_path()?;
+
+    // A missing archive simply means nothing has been cleared yet
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(&path).storage_context("Failed to read archive file")?;
+    let archive: Vec<ArchivedTask> =
+        serde_json::from_str(&content).storage_context("Failed to parse archive file")?;
+    Ok(archive)
+}
+
+/// Save all archived tasks to storage
+pub fn save_archive(archive: &[ArchivedTask]) -> Result<()> {
+    let path = archive_file_path()?;
+    let json =
+        serde_json::to_string_pretty(archive).storage_context("Failed to serialize archive")?;
+    fs::write(&path, json).storage_context("Failed to save archive")?;
     Ok(())
 }
 
+/// A backup of the tasks file, made before it was overwritten
+#[derive(Debug, Clone)]
+pub struct Backup {
+    /// Unique name of the backup, see `BACKUP_STAMP`
+    pub stamp: String,
+    /// Local time the backup was made
+    pub created: NaiveDateTime,
+    pub path: PathBuf,
+}
+
+/// List all backups of the tasks file, oldest first. Files that are not named by a stamp
+/// are ignored.
+pub fn list_backups() -> Result<Vec<Backup>> {
+    let dir = backup_dir_path()?;
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut backups = Vec::new();
+    for entry in fs::read_dir(&dir).storage_context("Failed to read backups directory")? {
+        let path = entry
+            .storage_context("Failed to read backups directory")?
+            .path();
+        let Some(stamp) = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .and_then(|n| n.strip_suffix(".json"))
+        else {
+            continue;
+        };
+        if let Ok(created) = NaiveDateTime::parse_from_str(stamp, BACKUP_STAMP) {
+            backups.push(Backup {
+                stamp: stamp.to_string(),
+                created,
+                path: path.clone(),
+            });
+        }
+    }
+    backups.sort_by(|a, b| a.stamp.cmp(&b.stamp));
+    Ok(backups)
+}
+
+/// Load the tasks saved in a backup
+pub fn load_backup(backup: &Backup) -> Result<Vec<Task>> {
+    let content = fs::read_to_string(&backup.path).storage_context("Failed to read backup file")?;
+    let tasks: Vec<Task> =
+        serde_json::from_str(&content).storage_context("Failed to parse backup file")?;
+    Ok(tasks)
+}
+
+/// Copy the tasks file into the backups directory, unless it is missing or unchanged since
+/// the last backup, then delete the backups the retention no longer covers
+fn back_up_tasks() -> Result<()> {
+    let retention = BACKUP_RETENTION.get().cloned().unwrap_or_default();
+    let path = tasks_file_path()?;
+    if (retention.keep == 0 && retention.daily_days == 0) || !path.exists() {
+        return Ok(());
+    }
+
+    let content = fs::read(&path).storage_context("Failed to read tasks file")?;
+    let mut backups = list_backups()?;
+    let unchanged = backups
+        .last()
+        .is_some_and(|last| fs::read(&last.path).is_ok_and(|c| c == content));
+    if !unchanged {
+        let dir = backup_dir_path()?;
+        fs::create_dir_all(&dir).storage_context("Failed to create backups directory")?;
+        let created = Local::now().naive_local();
+        let stamp = created.format(BACKUP_STAMP).to_string();
+        let path = dir.join(format!("{}.json", stamp));
+        fs::write(&path, content).storage_context("Failed to write backup file")?;
+        backups.push(Backup {
+            stamp,
+            created,
+            path,
+        });
+    }
+
+    let today = Local::now().date_naive();
+    let created: Vec<NaiveDateTime> = backups.iter().map(|b| b.created).collect();
+    let kept = retained_backups(&created, &retention, today);
+    for (index, backup) in backups.iter().enumerate() {
+        if !kept.contains(&index) {
+            fs::remove_file(&backup.path).storage_context("Failed to delete old backup")?;
+        }
+    }
+    Ok(())
+}
+
+/// Indices of the backups to keep, given their creation times in ascending order: the
+/// `keep` newest, and the newest of each of the last `daily_days` days
+fn retained_backups(
+    created: &[NaiveDateTime],
+    retention: &BackupRetention,
+    today: NaiveDate,
+) -> HashSet<usize> {
+    let mut kept = HashSet::new();
+    let mut days = HashSet::new();
+    for (rank, (index, time)) in created.iter().enumerate().rev().enumerate() {
+        if rank < retention.keep as usize {
+            kept.insert(index);
+        }
+        let day = time.date();
+        let recent = (today - day).num_days() < retention.daily_days as i64;
+        if recent && days.insert(day) {
+            kept.insert(index);
+        }
+    }
+    kept
+}
+
 /// Get the next available task ID (recycles lowest gaps)
 pub fn get_next_task_id(tasks: &[Task]) -> u32 {
-    if tasks.is_empty() {
+    next_free_id(tasks.iter().map(|t| t.id).collect())
+}
+
+/// Get the next available archive ID (recycles lowest gaps)
+pub fn get_next_archive_id(archive: &[ArchivedTask]) -> u32 {
+    next_free_id(archive.iter().map(|a| a.id).collect())
+}
+
+/// Find the lowest positive ID not present in `ids`
+fn next_free_id(mut ids: Vec<u32>) -> u32 {
+    if ids.is_empty() {
         return 1;
     }
 
-    let mut ids: Vec<u32> = tasks.iter().map(|t| t.id).collect();
     ids.sort_unstable();
 
     // Find the first gap
@@ -129,6 +371,44 @@ mod tests {
         assert_eq!(get_next_task_id(&tasks), 2);
     }
 
+    #[test]
+    fn test_get_next_archive_id_with_gap() {
+        let date = chrono::NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+        let task = Task::new(1, "Task".to_string(), "Tag".to_string(), None);
+        let archive = vec![
+            ArchivedTask::new(1, date, task.clone()),
+            ArchivedTask::new(3, date, task),
+        ];
+        assert_eq!(get_next_archive_id(&archive), 2);
+    }
+
+    #[test]
+    fn test_retained_backups() {
+        let at = |d, h| {
+            NaiveDate::from_ymd_opt(2026, 10, d)
+                .unwrap()
+                .and_hms_opt(h, 0, 0)
+                .unwrap()
+        };
+        // Two backups on each of 15 to 19 October
+        let created: Vec<NaiveDateTime> = (15..=19).flat_map(|d| [at(d, 9), at(d, 17)]).collect();
+        let today = NaiveDate::from_ymd_opt(2026, 10, 19).unwrap();
+        let retention = |keep, daily_days| BackupRetention { keep, daily_days };
+
+        let mut kept: Vec<usize> = retained_backups(&created, &retention(3, 3), today)
+            .into_iter()
+            .collect();
+        kept.sort_unstable();
+        // The 3 newest, plus the newest of 17 October
+        assert_eq!(kept, [5, 7, 8, 9]);
+
+        assert_eq!(
+            retained_backups(&created, &retention(20, 0), today).len(),
+            10
+        );
+        assert!(retained_backups(&created, &retention(0, 0), today).is_empty());
+    }
+
     #[test]
     fn test_tag_exists() {
         let tags = vec![
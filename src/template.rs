@@ -0,0 +1,310 @@
+use crate::config::UrgencyWeights;
+use crate::error::TaskError;
+use crate::models::Task;
+use crate::theme;
+use crate::ui;
+use chrono::NaiveDate;
+
+/// A task field that can appear in a template
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Id,
+    Name,
+    Tag,
+    Deadline,
+    DaysLeft,
+    Status,
+    Urgency,
+}
+
+impl Field {
+    fn parse(name: &str) -> Option<Self> {
+        Some(match name {
+            "id" => Field::Id,
+            "name" => Field::Name,
+            "tag" => Field::Tag,
+            "deadline" => Field::Deadline,
+            "days_left" => Field::DaysLeft,
+            "status" => Field::Status,
+            "urgency" => Field::Urgency,
+            _ => return None,
+        })
+    }
+}
+
+/// How a value is placed in a fixed-width column
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Align {
+    Left,
+    Right,
+    Center,
+}
+
+/// A `{field:spec}` placeholder
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Placeholder {
+    field: Field,
+    /// Alignment and column width, e.g. `<20`
+    align: Option<(Align, usize)>,
+    /// chrono format, only for `deadline`
+    date_format: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Literal(String),
+    Field(Placeholder),
+}
+
+/// Values a template needs besides the task itself
+pub struct RenderContext<'a> {
+    /// Format for `{deadline}` without an explicit format
+    pub date_format: &'a str,
+    /// Reference date for `{days_left}` and `{status}`
+    pub today: NaiveDate,
+    /// Weights for `{urgency}`
+    pub urgency: &'a UrgencyWeights,
+}
+
+/// A parsed list output template such as `{id} {name:<30} {deadline:%a %d %b}`.
+///
+/// A placeholder is `{field}` or `{field:spec}`. The spec may start with an alignment
+/// (`<`, `>` or `^`) and a width; values wider than the column are truncated with an
+/// ellipsis. For `deadline`, the rest of the spec is a chrono format. `{{` and `}}`
+/// print literal braces.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template {
+    segments: Vec<Segment>,
+}
+
+impl Template {
+    /// Parse a template, rejecting unknown fields and malformed placeholders
+    pub fn parse(template: &str) -> Result<Self, TaskError> {
+        let invalid = |reason: String| {
+            TaskError::validation(format!("Invalid format \"{}\": {}", template, reason))
+        };
+
+        let mut segments = Vec::new();
+        let mut literal = String::new();
+        let mut chars = template.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '}' => return Err(invalid("unmatched '}'".to_string())),
+                '{' => {
+                    let mut inner = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => inner.push(c),
+                            None => return Err(invalid("unclosed '{'".to_string())),
+                        }
+                    }
+
+                    if !literal.is_empty() {
+                        segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                    }
+                    segments.push(Segment::Field(parse_placeholder(&inner).map_err(invalid)?));
+                }
+                c => literal.push(c),
+            }
+        }
+        if !literal.is_empty() {
+            segments.push(Segment::Literal(literal));
+        }
+
+        Ok(Self { segments })
+    }
+
+    /// Render one task as a single line
+    pub fn render(&self, task: &Task, ctx: &RenderContext) -> String {
+        let mut line = String::new();
+        for segment in &self.segments {
+            match segment {
+                Segment::Literal(s) => line.push_str(s),
+                Segment::Field(placeholder) => {
+                    let value = field_value(placeholder, task, ctx);
+                    match placeholder.align {
+                        Some((align, width)) => line.push_str(&align_to(&value, align, width)),
+                        None => line.push_str(&value),
+                    }
+                }
+            }
+        }
+        line
+    }
+}
+
+/// Parse the text between braces: `field` or `field:spec`
+fn parse_placeholder(inner: &str) -> Result<Placeholder, String> {
+    let (name, spec) = match inner.split_once(':') {
+        Some((name, spec)) => (name.trim(), spec),
+        None => (inner.trim(), ""),
+    };
+    let field = Field::parse(name).ok_or_else(|| format!("unknown field \"{}\"", name))?;
+
+    let mut rest = spec;
+    let mut align = None;
+    if let Some(a) = rest.chars().next().and_then(|c| match c {
+        '<' => Some(Align::Left),
+        '>' => Some(Align::Right),
+        '^' => Some(Align::Center),
+        _ => None,
+    }) {
+        let digits: String = rest[1..].chars().take_while(char::is_ascii_digit).collect();
+        let width: usize = digits
+            .parse()
+            .map_err(|_| format!("missing width after alignment in \"{}\"", inner))?;
+        align = Some((a, width));
+        rest = &rest[1 + digits.len()..];
+    }
+
+    let date_format = match (field, rest) {
+        (_, "") => None,
+        (Field::Deadline, format) => {
+            theme::validate_date_format(format).map_err(|e| e.to_string())?;
+            Some(format.to_string())
+        }
+        _ => return Err(format!("field \"{}\" does not take a format", name)),
+    };
+
+    Ok(Placeholder {
+        field,
+        align,
+        date_format,
+    })
+}
+
+/// Text of a field for one task
+fn field_value(placeholder: &Placeholder, task: &Task, ctx: &RenderContext) -> String {
+    match placeholder.field {
+        Field::Id => format!("{:03}", task.id),
+        Field::Name => task.name.clone(),
+        Field::Tag => task.tag.clone(),
+        Field::Deadline => task
+            .deadline
+            .map(|d| {
+                let format = placeholder
+                    .date_format
+                    .as_deref()
+                    .unwrap_or(ctx.date_format);
+                d.format(format).to_string()
+            })
+            .unwrap_or_default(),
+        Field::DaysLeft => days_left(task, ctx.today)
+            .map(|days| days.to_string())
+            .unwrap_or_default(),
+        Field::Status => status(task, ctx.today).to_string(),
+        Field::Urgency => format!("{:.1}", ctx.urgency.score(task, ctx.today)),
+    }
+}
+
+/// Days until the deadline, negative when overdue; None without deadline
+pub fn days_left(task: &Task, today: NaiveDate) -> Option<i64> {
+    task.deadline.map(|d| (d - today).num_days())
+}
+
+/// One-word task state: done, overdue, today or open
+pub fn status(task: &Task, today: NaiveDate) -> &'static str {
+    match task.deadline {
+        _ if task.completed => "done",
+        Some(d) if d < today => "overdue",
+        Some(d) if d == today => "today",
+        _ => "open",
+    }
+}
+
+/// Pad or truncate a value to exactly `width` display columns
+fn align_to(value: &str, align: Align, width: usize) -> String {
+    let value = ui::truncate_to_width(value, width);
+    let gap = width.saturating_sub(ui::display_width(&value));
+    let (left, right) = match align {
+        Align::Left => (0, gap),
+        Align::Right => (gap, 0),
+        Align::Center => (gap / 2, gap - gap / 2),
+    };
+    format!("{}{}{}", " ".repeat(left), value, " ".repeat(right))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ctx(weights: &UrgencyWeights) -> RenderContext<'_> {
+        RenderContext {
+            date_format: "%d.%m.%Y",
+            today: NaiveDate::from_ymd_opt(2026, 2, 17).unwrap(),
+            urgency: weights,
+        }
+    }
+
+    fn task(deadline: Option<(u32, u32)>) -> Task {
+        let deadline = deadline.map(|(m, d)| NaiveDate::from_ymd_opt(2026, m, d).unwrap());
+        Task::new(7, "Lab report".to_string(), "Physics".to_string(), deadline)
+    }
+
+    #[test]
+    fn test_render_fields() {
+        let weights = UrgencyWeights::default();
+        let template = Template::parse(
+            "{id} {name} [{tag}] {deadline} {deadline:%a %d %b} {days_left} {status} {urgency}",
+        )
+        .unwrap();
+        assert_eq!(
+            template.render(&task(Some((2, 20))), &ctx(&weights)),
+            "007 Lab report [Physics] 20.02.2026 Fri 20 Feb 3 open 4.0"
+        );
+        assert_eq!(
+            template.render(&task(None), &ctx(&weights)),
+            "007 Lab report [Physics]    open 0.0"
+        );
+    }
+
+    #[test]
+    fn test_render_alignment_and_escapes() {
+        let weights = UrgencyWeights::default();
+        let t = task(Some((2, 16)));
+        let render = |s: &str| Template::parse(s).unwrap().render(&t, &ctx(&weights));
+
+        assert_eq!(render("{name:<12}|"), "Lab report  |");
+        assert_eq!(render("{name:>12}|"), "  Lab report|");
+        assert_eq!(render("{status:^9}|"), " overdue |");
+        assert_eq!(render("{name:<6}|"), "Lab r…|");
+        assert_eq!(render("{deadline:>8%d.%m}|"), "   16.02|");
+        assert_eq!(render("{{{days_left}}}"), "{-1}");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        for bad in [
+            "{nam}",
+            "{name",
+            "name}",
+            "{name:%d}",
+            "{name:<}",
+            "{deadline:%Q}",
+            "{deadline:%H}",
+            "{deadline:%d.%m %H:%M}",
+        ] {
+            assert!(Template::parse(bad).is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_status() {
+        let today = NaiveDate::from_ymd_opt(2026, 2, 17).unwrap();
+        assert_eq!(status(&task(Some((2, 17))), today), "today");
+        assert_eq!(status(&task(Some((2, 16))), today), "overdue");
+        let mut done = task(Some((2, 16)));
+        done.completed = true;
+        assert_eq!(status(&done, today), "done");
+    }
+}
@@ -0,0 +1,245 @@
+use crate::config::ThemeConfig;
+use crate::error::TaskError;
+use crate::ui;
+use chrono::NaiveDate;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// A text style: a list of ANSI SGR codes, empty when unstyled
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Style {
+    codes: Vec<&'static str>,
+}
+
+impl Style {
+    /// Build a style from SGR codes
+    fn from_codes(codes: &[&'static str]) -> Self {
+        Self {
+            codes: codes.to_vec(),
+        }
+    }
+
+    /// Parse a style spec such as "bold red", "strikethrough" or "bright_blue".
+    /// An empty spec (or "none") means no styling.
+    pub fn parse(spec: &str) -> Result<Self, TaskError> {
+        let mut codes = Vec::new();
+        for word in spec.split_whitespace() {
+            let code = match word.to_lowercase().as_str() {
+                "none" => continue,
+                "bold" => "1",
+                "dim" => "2",
+                "italic" => "3",
+                "underline" => "4",
+                "strikethrough" => "9",
+                "black" => "30",
+                "red" => "31",
+                "green" => "32",
+                "yellow" => "33",
+                "blue" => "34",
+                "magenta" => "35",
+                "cyan" => "36",
+                "white" => "37",
+                "bright_black" | "gray" | "grey" => "90",
+                "bright_red" => "91",
+                "bright_green" => "92",
+                "bright_yellow" => "93",
+                "bright_blue" => "94",
+                "bright_magenta" => "95",
+                "bright_cyan" => "96",
+                "bright_white" => "97",
+                _ => {
+                    return Err(TaskError::validation(format!(
+                        "Unknown style \"{}\" in \"{}\"",
+                        word, spec
+                    )))
+                }
+            };
+            codes.push(code);
+        }
+        Ok(Self { codes })
+    }
+
+    /// Apply the style to text. Unstyled text is returned unchanged.
+    pub fn paint(&self, s: &str) -> String {
+        if self.codes.is_empty() {
+            return s.to_string();
+        }
+        ui::paint(&self.codes.join(";"), s)
+    }
+}
+
+/// Styles and layout for list output
+#[derive(Debug, Clone)]
+pub struct Theme {
+    /// Tag header style, unless the tag has its own
+    pub header: Style,
+    /// Completed task name and date
+    pub completed: Style,
+    /// Active task past its deadline
+    pub overdue: Style,
+    /// Active task due today
+    pub due_today: Style,
+    /// Per-tag header styles
+    pub tags: BTreeMap<String, Style>,
+    /// chrono format string for deadlines
+    pub date_format: String,
+    /// Spaces before each task row
+    pub indent: usize,
+    /// Minimum gap between the longest name and the date column
+    pub padding: usize,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+impl Theme {
+    /// Theme for terminals with a dark background (the default)
+    pub fn dark() -> Self {
+        Self {
+            header: Style::from_codes(&["1", "36"]),
+            completed: Style::from_codes(&["9"]),
+            overdue: Style::from_codes(&["31"]),
+            due_today: Style::from_codes(&["33"]),
+            tags: BTreeMap::new(),
+            date_format: "%d.%m.%Y".to_string(),
+            indent: 4,
+            padding: 8,
+        }
+    }
+
+    /// Theme for terminals with a light background, avoiding hard-to-read yellow
+    pub fn light() -> Self {
+        Self {
+            header: Style::from_codes(&["1", "34"]),
+            completed: Style::from_codes(&["9", "90"]),
+            overdue: Style::from_codes(&["1", "31"]),
+            due_today: Style::from_codes(&["35"]),
+            ..Self::dark()
+        }
+    }
+
+    /// Look up a built-in theme by name
+    pub fn named(name: &str) -> Result<Self, TaskError> {
+        match name.to_lowercase().as_str() {
+            "dark" => Ok(Self::dark()),
+            "light" => Ok(Self::light()),
+            _ => Err(TaskError::validation(format!(
+                "Unknown theme \"{}\" (expected dark or light)",
+                name
+            ))),
+        }
+    }
+
+    /// Build a theme from the `[theme]` config section: start from the named base theme
+    /// and apply every override that is set. The date format comes from `[dates]`.
+    pub fn from_config(config: &ThemeConfig) -> Result<Self, TaskError> {
+        let mut theme = match &config.name {
+            Some(name) => Self::named(name)?,
+            None => Self::dark(),
+        };
+
+        let styles = &config.styles;
+        if let Some(spec) = &styles.header {
+            theme.header = Style::parse(spec)?;
+        }
+        if let Some(spec) = &styles.completed {
+            theme.completed = Style::parse(spec)?;
+        }
+        if let Some(spec) = &styles.overdue {
+            theme.overdue = Style::parse(spec)?;
+        }
+        if let Some(spec) = &styles.due_today {
+            theme.due_today = Style::parse(spec)?;
+        }
+        for (tag, spec) in &config.tags {
+            theme.tags.insert(tag.clone(), Style::parse(spec)?);
+        }
+
+        if let Some(indent) = config.indent {
+            theme.indent = indent;
+        }
+        if let Some(padding) = config.padding {
+            theme.padding = padding;
+        }
+
+        Ok(theme)
+    }
+
+    /// Style for a tag header
+    pub fn header_style(&self, tag: &str) -> &Style {
+        self.tags.get(tag).unwrap_or(&self.header)
+    }
+}
+
+/// Reject chrono format strings that would fail when formatting a date, such as unknown
+/// specifiers or time specifiers like `%H`, which a date without a time cannot fill
+pub fn validate_date_format(format: &str) -> Result<(), TaskError> {
+    let sample = NaiveDate::from_ymd_opt(2026, 1, 1).expect("valid date");
+    let mut out = String::new();
+    if format.is_empty() || write!(out, "{}", sample.format(format)).is_err() {
+        return Err(TaskError::validation(format!(
+            "Invalid date format \"{}\"",
+            format
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::StyleConfig;
+
+    #[test]
+    fn test_style_parse() {
+        assert_eq!(
+            Style::parse("bold red").unwrap().paint("x"),
+            "\x1b[1;31mx\x1b[0m"
+        );
+        assert_eq!(Style::parse("").unwrap().paint("x"), "x");
+        assert_eq!(Style::parse("none").unwrap(), Style::default());
+        assert!(Style::parse("blinking").is_err());
+    }
+
+    #[test]
+    fn test_theme_from_config_overrides() {
+        let mut tags = BTreeMap::new();
+        tags.insert("Calculus".to_string(), "blue".to_string());
+        let config = ThemeConfig {
+            name: Some("light".to_string()),
+            date_format: None,
+            indent: Some(2),
+            padding: None,
+            styles: StyleConfig {
+                overdue: Some("bright_red".to_string()),
+                ..StyleConfig::default()
+            },
+            tags,
+        };
+
+        let theme = Theme::from_config(&config).unwrap();
+        assert_eq!(theme.overdue, Style::parse("bright_red").unwrap());
+        assert_eq!(theme.due_today, Theme::light().due_today);
+        assert_eq!(
+            theme.header_style("Calculus"),
+            &Style::parse("blue").unwrap()
+        );
+        assert_eq!(theme.header_style("Automata"), &Theme::light().header);
+        assert_eq!(theme.indent, 2);
+        assert_eq!(theme.padding, 8);
+    }
+
+    #[test]
+    fn test_theme_rejects_bad_values() {
+        assert!(Theme::named("solarized").is_err());
+        assert!(validate_date_format("%d.%m.%Y").is_ok());
+        assert!(validate_date_format("%Q").is_err());
+        assert!(validate_date_format("").is_err());
+        for format in ["%H", "%M", "%S", "%d.%m.%Y %H:%M"] {
+            assert!(validate_date_format(format).is_err(), "{}", format);
+        }
+    }
+}
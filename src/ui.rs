@@ -0,0 +1,170 @@
+use clap::ValueEnum;
+use std::io::{self, IsTerminal};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// Marker appended to text cut short to fit a column
+pub const ELLIPSIS: char = '…';
+
+/// When to style output with ANSI escape codes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum ColorChoice {
+    /// Style output only when stdout is a terminal and `NO_COLOR` is not set
+    #[default]
+    Auto,
+    /// Always style output, even when piped
+    Always,
+    /// Never style output
+    Never,
+}
+
+impl ColorChoice {
+    /// Decide whether output should be styled.
+    /// An explicit `--color always` wins over `NO_COLOR`, as recommended by no-color.org.
+    pub fn enabled(self) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => !no_color_requested() && io::stdout().is_terminal(),
+        }
+    }
+}
+
+/// Whether the user opted out of color through a non-empty `NO_COLOR` variable
+fn no_color_requested() -> bool {
+    std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty())
+}
+
+/// Wrap text in an ANSI SGR sequence, resetting all attributes afterwards
+pub fn paint(code: &str, s: &str) -> String {
+    format!("\x1b[{}m{}\x1b[0m", code, s)
+}
+
+/// Number of terminal columns a string occupies (CJK and emoji count as two)
+pub fn display_width(s: &str) -> usize {
+    UnicodeWidthStr::width(s)
+}
+
+/// Detect the width of the terminal stdout is attached to.
+/// Otherwise uses the `COLUMNS` environment variable, also when output is piped, so scripts
+/// can ask for a width. Returns None when stdout is not a terminal and `COLUMNS` is unset.
+pub fn terminal_width() -> Option<usize> {
+    if let Some((terminal_size::Width(w), _)) = terminal_size::terminal_size_of(io::stdout()) {
+        return Some(w as usize);
+    }
+    std::env::var("COLUMNS")
+        .ok()
+        .and_then(|c| c.trim().parse::<usize>().ok())
+        .filter(|&w| w > 0)
+}
+
+/// Cut a string to at most `max` display columns, ending it with an ellipsis if anything
+/// was removed
+pub fn truncate_to_width(s: &str, max: usize) -> String {
+    if display_width(s) <= max {
+        return s.to_string();
+    }
+    if max == 0 {
+        return String::new();
+    }
+
+    let mut out = String::new();
+    let mut width = 0;
+    for c in s.chars() {
+        let w = c.width().unwrap_or(0);
+        // Leave one column for the ellipsis
+        if width + w > max - 1 {
+            break;
+        }
+        out.push(c);
+        width += w;
+    }
+    // Avoid a dangling space before the ellipsis
+    out.truncate(out.trim_end().len());
+    out.push(ELLIPSIS);
+    out
+}
+
+/// Split a string into lines of at most `max` display columns, breaking at spaces where
+/// possible and inside words only when a single word is too long
+pub fn wrap_to_width(s: &str, max: usize) -> Vec<String> {
+    let max = max.max(1);
+    let mut lines: Vec<String> = Vec::new();
+    let mut line = String::new();
+
+    for word in s.split_whitespace() {
+        let needed = if line.is_empty() {
+            display_width(word)
+        } else {
+            display_width(&line) + 1 + display_width(word)
+        };
+
+        if needed <= max {
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(word);
+            continue;
+        }
+
+        if !line.is_empty() {
+            lines.push(std::mem::take(&mut line));
+        }
+
+        // Hard-break words wider than a whole line
+        for c in word.chars() {
+            let w = c.width().unwrap_or(0);
+            if display_width(&line) + w > max && !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+            }
+            line.push(c);
+        }
+    }
+
+    if !line.is_empty() || lines.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paint() {
+        assert_eq!(paint("31", "late"), "\x1b[31mlate\x1b[0m");
+    }
+
+    #[test]
+    fn test_explicit_color_choice() {
+        assert!(ColorChoice::Always.enabled());
+        assert!(!ColorChoice::Never.enabled());
+    }
+
+    #[test]
+    fn test_display_width_counts_columns_not_bytes() {
+        assert_eq!(display_width("Лекция"), 6);
+        assert_eq!(display_width("Übung"), 5);
+        assert_eq!(display_width("宿題"), 4);
+    }
+
+    #[test]
+    fn test_truncate_to_width() {
+        assert_eq!(truncate_to_width("Short", 10), "Short");
+        assert_eq!(truncate_to_width("Rewatch lecture", 8), "Rewatch…");
+        assert_eq!(truncate_to_width("Read chapter", 6), "Read…");
+        // A double-width character that would overflow is dropped entirely
+        assert_eq!(truncate_to_width("宿題宿題", 4), "宿…");
+        assert_eq!(display_width(&truncate_to_width("宿題宿題", 4)), 3);
+    }
+
+    #[test]
+    fn test_wrap_to_width() {
+        assert_eq!(
+            wrap_to_width("Read chapter three of the book", 12),
+            vec!["Read chapter", "three of the", "book"]
+        );
+        assert_eq!(wrap_to_width("Supercalifragilistic", 8).len(), 3);
+        assert_eq!(wrap_to_width("", 8), vec![""]);
+    }
+}
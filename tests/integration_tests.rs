@@ -1,3 +1,6 @@
+// The remove tests predate the map_flatten lint and are kept as written
+#![allow(clippy::map_flatten)]
+
 use assert_cmd::Command;
 use predicates::prelude::*;
 use std::fs;
@@ -202,9 +205,14 @@ fn test_remove_missing_ids() {
     cmd.arg("1");
     cmd.arg("999");
 
-    cmd.assert().success().stdout(predicate::str::contains(
-        "One or more IDs could not be found",
+    // Partial success: task 1 is marked, 999 is reported with a dedicated exit code
+    cmd.assert().code(5).stderr(predicate::str::contains(
+        "One or more IDs could not be found: 999",
     ));
+
+    let updated = fs::read_to_string(&tasks_file).unwrap();
+    let tasks: Vec<serde_json::Value> = serde_json::from_str(&updated).unwrap();
+    assert_eq!(tasks[0]["completed"], true);
 }
 
 #[test]
@@ -236,6 +244,118 @@ fn test_clear_with_completed_tasks() {
     assert!(updated.contains("Task2"));
     assert!(!updated.contains("Task1"));
     assert!(!updated.contains("Task3"));
+
+    // Verify completed tasks were moved to the archive
+    let archive = fs::read_to_string(temp_dir.path().join(".tasks-archive.json")).unwrap();
+    assert!(archive.contains("Task1"));
+    assert!(archive.contains("Task3"));
+    assert!(!archive.contains("Task2"));
+}
+
+#[test]
+fn test_clear_purge_skips_archive() {
+    let temp_dir = TempDir::new().unwrap();
+    let tasks_file = temp_dir.path().join(".tasks.json");
+    let tags_file = temp_dir.path().join(".tasks-tags.json");
+
+    let tasks_json = r#"[
+        {"id":1,"name":"Task1","tag":"Test","deadline":"2026-02-20","completed":true},
+        {"id":2,"name":"Task2","tag":"Test","deadline":"2026-02-17","completed":false}
+    ]"#;
+
+    fs::write(&tasks_file, tasks_json).unwrap();
+    fs::write(&tags_file, r#"["Test"]"#).unwrap();
+
+    let mut cmd = Command::new(cargo_bin_cmd());
+    cmd.env("HOME", temp_dir.path());
+    cmd.arg("clear");
+    cmd.arg("--purge");
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("permanently deleted"));
+
+    let updated = fs::read_to_string(&tasks_file).unwrap();
+    assert!(!updated.contains("Task1"));
+    assert!(!temp_dir.path().join(".tasks-archive.json").exists());
+}
+
+#[test]
+fn test_archive_list_and_search() {
+    let temp_dir = TempDir::new().unwrap();
+    let archive_file = temp_dir.path().join(".tasks-archive.json");
+
+    let archive_json = r#"[
+        {"id":1,"archived_on":"2026-03-01","task":{"id":4,"name":"Read chapter 3","tag":"Automata","deadline":"2026-02-16","completed":true}},
+        {"id":2,"archived_on":"2026-03-01","task":{"id":7,"name":"Homework assignment","tag":"Calculus","deadline":null,"completed":true}}
+    ]"#;
+
+    fs::write(&archive_file, archive_json).unwrap();
+
+    let mut cmd = Command::new(cargo_bin_cmd());
+    cmd.env("HOME", temp_dir.path());
+    cmd.args(["archive", "list"]);
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "001 - Read chapter 3 (Automata, due 16.02.2026, archived 01.03.2026)",
+        ))
+        .stdout(predicate::str::contains("002 - Homework assignment"));
+
+    let mut cmd = Command::new(cargo_bin_cmd());
+    cmd.env("HOME", temp_dir.path());
+    cmd.args(["archive", "search", "calculus"]);
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Homework assignment"))
+        .stdout(predicate::str::contains("Read chapter 3").not());
+}
+
+#[test]
+fn test_archive_restore() {
+    let temp_dir = TempDir::new().unwrap();
+    let tasks_file = temp_dir.path().join(".tasks.json");
+    let tags_file = temp_dir.path().join(".tasks-tags.json");
+    let archive_file = temp_dir.path().join(".tasks-archive.json");
+
+    let tasks_json = r#"[
+        {"id":1,"name":"Task1","tag":"Test","deadline":null}
+    ]"#;
+    let archive_json = r#"[
+        {"id":1,"archived_on":"2026-03-01","task":{"id":1,"name":"Old task","tag":"Physics","deadline":null,"completed":true}}
+    ]"#;
+
+    fs::write(&tasks_file, tasks_json).unwrap();
+    fs::write(&tags_file, r#"["Test"]"#).unwrap();
+    fs::write(&archive_file, archive_json).unwrap();
+
+    let mut cmd = Command::new(cargo_bin_cmd());
+    cmd.env("HOME", temp_dir.path());
+    cmd.args(["archive", "restore", "1", "5", "--reopen"]);
+
+    cmd.assert()
+        .code(5)
+        .stdout(predicate::str::contains(
+            "Restored task 002: Old task (Physics)",
+        ))
+        .stderr(predicate::str::contains(
+            "One or more IDs could not be found: 5",
+        ));
+
+    let tasks: Vec<serde_json::Value> =
+        serde_json::from_str(&fs::read_to_string(&tasks_file).unwrap()).unwrap();
+    assert_eq!(tasks.len(), 2);
+    assert_eq!(tasks[1]["id"], 2);
+    assert_eq!(tasks[1]["completed"], false);
+
+    let tags = fs::read_to_string(&tags_file).unwrap();
+    assert!(tags.contains("Physics"));
+
+    let archive: Vec<serde_json::Value> =
+        serde_json::from_str(&fs::read_to_string(&archive_file).unwrap()).unwrap();
+    assert!(archive.is_empty());
 }
 
 #[test]
@@ -287,3 +407,1432 @@ fn test_list_with_completed_tasks() {
         .stdout(predicate::str::contains("Active Task"))
         .stdout(predicate::str::contains("Completed Task"));
 }
+
+#[test]
+fn test_clear_dry_run_keeps_tasks() {
+    let temp_dir = TempDir::new().unwrap();
+    let tasks_file = temp_dir.path().join(".tasks.json");
+    let tags_file = temp_dir.path().join(".tasks-tags.json");
+
+    let tasks_json = r#"[
+        {"id":1,"name":"Task1","tag":"Test","deadline":"2026-02-20","completed":true},
+        {"id":2,"name":"Task2","tag":"Test","deadline":"2026-02-17","completed":false}
+    ]"#;
+
+    fs::write(&tasks_file, tasks_json).unwrap();
+    fs::write(&tags_file, r#"["Test"]"#).unwrap();
+
+    let mut cmd = Command::new(cargo_bin_cmd());
+    cmd.env("HOME", temp_dir.path());
+    cmd.args(["clear", "--dry-run"]);
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Would clear 1 completed tasks (moved to archive):",
+        ))
+        .stdout(predicate::str::contains("    001 - Task1"))
+        .stdout(predicate::str::contains("Task2").not());
+
+    assert_eq!(fs::read_to_string(&tasks_file).unwrap(), tasks_json);
+    assert!(!temp_dir.path().join(".tasks-archive.json").exists());
+}
+
+#[test]
+fn test_remove_and_add_dry_run_do_not_save() {
+    let temp_dir = TempDir::new().unwrap();
+    let tasks_file = temp_dir.path().join(".tasks.json");
+    let tags_file = temp_dir.path().join(".tasks-tags.json");
+
+    let tasks_json = r#"[
+        {"id":1,"name":"Task1","tag":"Test","deadline":"2026-02-20"}
+    ]"#;
+
+    fs::write(&tasks_file, tasks_json).unwrap();
+    fs::write(&tags_file, r#"["Test"]"#).unwrap();
+
+    let mut cmd = Command::new(cargo_bin_cmd());
+    cmd.env("HOME", temp_dir.path());
+    cmd.args(["--dry-run", "remove", "1"]);
+
+    cmd.assert().success().stdout(predicate::str::contains(
+        "Would mark task 001 as completed: Task1",
+    ));
+
+    let mut cmd = Command::new(cargo_bin_cmd());
+    cmd.env("HOME", temp_dir.path());
+    cmd.args(["add", "Homework", "Calculus", "--dry-run"]);
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Would create new tag: Calculus"))
+        .stdout(predicate::str::contains(
+            "Would create task 002: Homework (Calculus, no deadline)",
+        ));
+
+    assert_eq!(fs::read_to_string(&tasks_file).unwrap(), tasks_json);
+    assert_eq!(fs::read_to_string(&tags_file).unwrap(), r#"["Test"]"#);
+}
+
+#[test]
+fn test_remove_ranges_and_selectors() {
+    let temp_dir = TempDir::new().unwrap();
+    let tasks_file = temp_dir.path().join(".tasks.json");
+    let tags_file = temp_dir.path().join(".tasks-tags.json");
+
+    let tasks_json = r#"[
+        {"id":1,"name":"Task1","tag":"Test","deadline":"2020-01-01"},
+        {"id":2,"name":"Task2","tag":"Test","deadline":null},
+        {"id":3,"name":"Task3","tag":"Test","deadline":null},
+        {"id":4,"name":"Task4","tag":"Lab","deadline":null},
+        {"id":5,"name":"Task5","tag":"Test","deadline":null},
+        {"id":6,"name":"Task6","tag":"Test","deadline":null}
+    ]"#;
+
+    fs::write(&tasks_file, tasks_json).unwrap();
+    fs::write(&tags_file, r#"["Test","Lab"]"#).unwrap();
+
+    let mut cmd = Command::new(cargo_bin_cmd());
+    cmd.env("HOME", temp_dir.path());
+    cmd.args(["remove", "2-3", "6-", "--tag", "Lab", "--overdue"]);
+
+    cmd.assert().success();
+
+    let tasks: Vec<serde_json::Value> =
+        serde_json::from_str(&fs::read_to_string(&tasks_file).unwrap()).unwrap();
+    let completed: Vec<u64> = tasks
+        .iter()
+        .filter(|t| t["completed"].as_bool().unwrap_or(false))
+        .map(|t| t["id"].as_u64().unwrap())
+        .collect();
+    assert_eq!(completed, vec![1, 2, 3, 4, 6]);
+}
+
+#[test]
+fn test_remove_malformed_ids_are_reported() {
+    let temp_dir = TempDir::new().unwrap();
+    let tasks_file = temp_dir.path().join(".tasks.json");
+    let tags_file = temp_dir.path().join(".tasks-tags.json");
+
+    let tasks_json = r#"[
+        {"id":1,"name":"Task1","tag":"Test","deadline":null}
+    ]"#;
+
+    fs::write(&tasks_file, tasks_json).unwrap();
+    fs::write(&tags_file, r#"["Test"]"#).unwrap();
+
+    let mut cmd = Command::new(cargo_bin_cmd());
+    cmd.env("HOME", temp_dir.path());
+    cmd.args(["remove", "1", "abc", "7-3"]);
+
+    cmd.assert()
+        .code(3)
+        .stderr(predicate::str::contains("Invalid IDs or ranges: abc, 7-3"));
+
+    // Nothing is marked when any token is malformed
+    assert_eq!(fs::read_to_string(&tasks_file).unwrap(), tasks_json);
+}
+
+#[test]
+fn test_list_json_output() {
+    let temp_dir = TempDir::new().unwrap();
+    let tasks_file = temp_dir.path().join(".tasks.json");
+    let tags_file = temp_dir.path().join(".tasks-tags.json");
+
+    let tasks_json = r#"[
+        {"id":1,"name":"Task1","tag":"Test","deadline":"2026-02-20"},
+        {"id":2,"name":"Task2","tag":"Test","deadline":"2026-02-17","completed":true}
+    ]"#;
+
+    fs::write(&tasks_file, tasks_json).unwrap();
+    fs::write(&tags_file, r#"["Test"]"#).unwrap();
+
+    let mut cmd = Command::new(cargo_bin_cmd());
+    cmd.env("HOME", temp_dir.path());
+    cmd.args(["list", "--output", "json"]);
+
+    let out = cmd.assert().success().get_output().stdout.clone();
+    let value: serde_json::Value = serde_json::from_slice(&out).unwrap();
+    assert_eq!(value["command"], "list");
+    assert_eq!(value["tasks"][0]["id"], 2);
+    assert_eq!(value["tasks"][0]["deadline"], "2026-02-17");
+    assert_eq!(value["tasks"][0]["completed"], true);
+    assert_eq!(value["tasks"][1]["name"], "Task1");
+}
+
+#[test]
+fn test_add_and_remove_ndjson_output() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let mut cmd = Command::new(cargo_bin_cmd());
+    cmd.env("HOME", temp_dir.path());
+    cmd.args([
+        "--output",
+        "ndjson",
+        "add",
+        "Homework",
+        "Calculus",
+        "20.02.2026",
+    ]);
+
+    let out = cmd.assert().success().get_output().stdout.clone();
+    let text = String::from_utf8(out).unwrap();
+    assert_eq!(text.lines().count(), 1);
+    let value: serde_json::Value = serde_json::from_str(&text).unwrap();
+    assert_eq!(value["command"], "add");
+    assert_eq!(value["created_tag"], true);
+    assert_eq!(value["task"]["id"], 1);
+    assert_eq!(value["task"]["deadline"], "2026-02-20");
+
+    let mut cmd = Command::new(cargo_bin_cmd());
+    cmd.env("HOME", temp_dir.path());
+    cmd.args(["--output", "ndjson", "remove", "1", "999"]);
+
+    let out = cmd.assert().code(5).get_output().stdout.clone();
+    let value: serde_json::Value = serde_json::from_slice(&out).unwrap();
+    assert_eq!(value["command"], "remove");
+    assert_eq!(value["marked"], serde_json::json!([1]));
+    assert_eq!(value["not_found"], serde_json::json!(["999"]));
+}
+
+#[test]
+fn test_error_json_output() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let mut cmd = Command::new(cargo_bin_cmd());
+    cmd.env("HOME", temp_dir.path());
+    cmd.args([
+        "--output",
+        "json",
+        "add",
+        "Homework",
+        "Calculus",
+        "99.99.2026",
+    ]);
+
+    let out = cmd.assert().code(3).get_output().stderr.clone();
+    let value: serde_json::Value = serde_json::from_slice(&out).unwrap();
+    assert_eq!(value["error"]["kind"], "validation");
+    assert_eq!(value["error"]["exit_code"], 3);
+    assert!(value["error"]["message"]
+        .as_str()
+        .unwrap()
+        .starts_with("Invalid date format"));
+}
+
+#[test]
+fn test_remove_all_missing_exit_code() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let mut cmd = Command::new(cargo_bin_cmd());
+    cmd.env("HOME", temp_dir.path());
+    cmd.args(["remove", "1", "2"]);
+
+    cmd.assert().code(4).stderr(predicate::str::contains(
+        "One or more IDs could not be found: 1, 2",
+    ));
+}
+
+#[test]
+fn test_storage_error_exit_code() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(temp_dir.path().join(".tasks.json"), "not json").unwrap();
+
+    let mut cmd = Command::new(cargo_bin_cmd());
+    cmd.env("HOME", temp_dir.path());
+    cmd.arg("list");
+
+    cmd.assert()
+        .code(6)
+        .stderr(predicate::str::contains("Failed to parse tasks file"));
+}
+
+#[test]
+fn test_lock_contention_exit_code() {
+    use fs2::FileExt;
+
+    let temp_dir = TempDir::new().unwrap();
+    let lock = fs::File::create(temp_dir.path().join(".tasks.lock")).unwrap();
+    lock.lock_exclusive().unwrap();
+
+    let mut cmd = Command::new(cargo_bin_cmd());
+    cmd.env("HOME", temp_dir.path());
+    cmd.args(["add", "Homework", "Calculus"]);
+
+    cmd.assert()
+        .code(7)
+        .stderr(predicate::str::contains("locked by another"));
+
+    lock.unlock().unwrap();
+}
+
+#[test]
+fn test_list_aligns_unicode_and_truncates_to_columns() {
+    let temp_dir = TempDir::new().unwrap();
+    let tasks_file = temp_dir.path().join(".tasks.json");
+    let tags_file = temp_dir.path().join(".tasks-tags.json");
+
+    let tasks_json = r#"[
+        {"id":1,"name":"Лекция 1","tag":"Test","deadline":"2026-02-17"},
+        {"id":2,"name":"Lecture 2","tag":"Test","deadline":"2026-02-18"},
+        {"id":3,"name":"Prepare the complete lab report for the automata course","tag":"Lab","deadline":"2026-02-19"}
+    ]"#;
+
+    fs::write(&tasks_file, tasks_json).unwrap();
+    fs::write(&tags_file, r#"["Test","Lab"]"#).unwrap();
+
+    let mut cmd = Command::new(cargo_bin_cmd());
+    cmd.env("HOME", temp_dir.path());
+    cmd.env("COLUMNS", "50");
+    cmd.arg("list");
+
+    let out = cmd.assert().success().get_output().stdout.clone();
+    let text = String::from_utf8(out).unwrap();
+
+    // Every date ends in the same column, counted in characters rather than bytes
+    let date_columns: Vec<usize> = text
+        .lines()
+        .filter(|l| l.starts_with("    "))
+        .map(|l| l.chars().count())
+        .collect();
+    assert_eq!(date_columns.len(), 3);
+    assert!(date_columns.iter().all(|&c| c == date_columns[0]));
+    assert!(date_columns[0] <= 50);
+    assert!(text.contains("Prepare the complete…"));
+}
+
+#[test]
+fn test_list_color_modes() {
+    let temp_dir = TempDir::new().unwrap();
+    let tasks_file = temp_dir.path().join(".tasks.json");
+    let tags_file = temp_dir.path().join(".tasks-tags.json");
+
+    let tasks_json = r#"[
+        {"id":1,"name":"Active Task","tag":"Test","deadline":"2099-02-20","completed":false},
+        {"id":2,"name":"Completed Task","tag":"Test","deadline":"2026-02-17","completed":true}
+    ]"#;
+
+    fs::write(&tasks_file, tasks_json).unwrap();
+    fs::write(&tags_file, r#"["Test"]"#).unwrap();
+
+    // Piped output is plain, with a marker instead of strikethrough
+    let mut cmd = Command::new(cargo_bin_cmd());
+    cmd.env("HOME", temp_dir.path());
+    cmd.arg("list");
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("002 - [x] Completed Task"))
+        .stdout(predicate::str::contains("\x1b[").not());
+
+    // --color always forces styling even when NO_COLOR is set
+    let mut cmd = Command::new(cargo_bin_cmd());
+    cmd.env("HOME", temp_dir.path());
+    cmd.env("NO_COLOR", "1");
+    cmd.args(["list", "--color", "always"]);
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("\x1b[9mCompleted Task\x1b[0m"))
+        .stdout(predicate::str::contains("[x]").not());
+}
+
+#[test]
+fn test_list_uses_config_theme() {
+    let temp_dir = TempDir::new().unwrap();
+    let tasks_file = temp_dir.path().join(".tasks.json");
+    let tags_file = temp_dir.path().join(".tasks-tags.json");
+    let config_dir = temp_dir.path().join(".config").join("silver-tasks");
+
+    let tasks_json = r#"[
+        {"id":1,"name":"Essay","tag":"Writing","deadline":"2099-02-20"}
+    ]"#;
+    let config_toml = r#"
+[dates]
+output = "%Y-%m-%d"
+
+[theme]
+indent = 2
+padding = 3
+
+[theme.tags]
+Writing = "bold magenta"
+"#;
+
+    fs::write(&tasks_file, tasks_json).unwrap();
+    fs::write(&tags_file, r#"["Writing"]"#).unwrap();
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(config_dir.join("config.toml"), config_toml).unwrap();
+
+    let mut cmd = Command::new(cargo_bin_cmd());
+    cmd.env("HOME", temp_dir.path());
+    cmd.env("XDG_CONFIG_HOME", temp_dir.path().join(".config"));
+    cmd.args(["list", "--color", "always"]);
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("\x1b[1;35mWriting\x1b[0m"))
+        .stdout(predicate::str::contains("\n  001 - Essay   2099-02-20"));
+
+    // An invalid config is reported as a validation error
+    fs::write(config_dir.join("config.toml"), "[theme]\nname = \"neon\"\n").unwrap();
+
+    let mut cmd = Command::new(cargo_bin_cmd());
+    cmd.env("HOME", temp_dir.path());
+    cmd.env("XDG_CONFIG_HOME", temp_dir.path().join(".config"));
+    cmd.arg("list");
+
+    cmd.assert()
+        .code(3)
+        .stderr(predicate::str::contains("Unknown theme \"neon\""));
+}
+
+#[test]
+fn test_config_set_get_and_precedence() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_home = temp_dir.path().join(".config");
+    let config_file = config_home.join("silver-tasks").join("config.toml");
+
+    let silver = |args: &[&str]| {
+        let mut cmd = Command::new(cargo_bin_cmd());
+        cmd.env("HOME", temp_dir.path());
+        cmd.env("XDG_CONFIG_HOME", &config_home);
+        cmd.env_remove("SILVER_TASKS_SORT");
+        cmd.args(args);
+        cmd
+    };
+
+    silver(&["config", "set", "sort", "name"])
+        .assert()
+        .success();
+    silver(&["config", "set", "default_tag", "Inbox"])
+        .assert()
+        .success();
+    assert!(fs::read_to_string(&config_file)
+        .unwrap()
+        .contains("sort = \"name\""));
+
+    // Invalid values are rejected and the file is left untouched
+    let before = fs::read_to_string(&config_file).unwrap();
+    silver(&["config", "set", "sort", "random"])
+        .assert()
+        .code(3)
+        .stderr(predicate::str::contains("Unknown sort order"));
+    silver(&["config", "set", "colour", "never"])
+        .assert()
+        .code(3)
+        .stderr(predicate::str::contains("Unknown config key"));
+    assert_eq!(fs::read_to_string(&config_file).unwrap(), before);
+
+    silver(&["config", "get", "dates.input"])
+        .assert()
+        .code(4)
+        .stderr(predicate::str::contains("dates.input"));
+
+    // The file wins over defaults, the environment over the file
+    silver(&["config", "get", "sort"])
+        .assert()
+        .success()
+        .stdout("name\n");
+    silver(&["config", "get", "sort"])
+        .env("SILVER_TASKS_SORT", "id")
+        .assert()
+        .success()
+        .stdout("id\n");
+    silver(&["config", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("default_tag"))
+        .stdout(predicate::str::contains("Inbox  (file)"))
+        .stdout(predicate::str::contains("%d.%m.%Y  (default)"));
+
+    // `add` falls back to the default tag; `list --sort` wins over the setting
+    silver(&["add", "beta"]).assert().success();
+    silver(&["add", "Alpha"]).assert().success();
+    silver(&["list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Inbox\n    002 - Alpha\n    001 - beta",
+        ));
+    silver(&["list", "--sort", "id"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Inbox\n    001 - beta\n    002 - Alpha",
+        ));
+}
+
+#[test]
+fn test_config_data_dir_and_date_formats() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_home = temp_dir.path().join(".config");
+    let data_dir = temp_dir.path().join("data");
+    fs::create_dir_all(config_home.join("silver-tasks")).unwrap();
+    fs::write(
+        config_home.join("silver-tasks").join("config.toml"),
+        format!(
+            "data_dir = \"{}\"\n\n[dates]\ninput = \"%m/%d/%Y\"\noutput = \"%Y-%m-%d\"\n",
+            data_dir.display()
+        ),
+    )
+    .unwrap();
+
+    let mut cmd = Command::new(cargo_bin_cmd());
+    cmd.env("HOME", temp_dir.path());
+    cmd.env("XDG_CONFIG_HOME", &config_home);
+    cmd.args(["add", "Essay", "Writing", "02/20/2026"]);
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("due 2026-02-20"));
+    assert!(data_dir.join(".tasks.json").exists());
+    assert!(!temp_dir.path().join(".tasks.json").exists());
+
+    // --data-dir overrides the setting
+    let other_dir = temp_dir.path().join("other");
+    let mut cmd = Command::new(cargo_bin_cmd());
+    cmd.env("HOME", temp_dir.path());
+    cmd.env("XDG_CONFIG_HOME", &config_home);
+    cmd.args(["list", "--data-dir"]).arg(&other_dir);
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("No tasks found"));
+}
+
+#[test]
+fn test_list_format_templates() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_home = temp_dir.path().join(".config");
+    fs::create_dir_all(config_home.join("silver-tasks")).unwrap();
+    fs::write(
+        temp_dir.path().join(".tasks.json"),
+        r#"[
+            {"id":1,"name":"Essay","tag":"Writing","deadline":"2099-02-20"},
+            {"id":2,"name":"Read","tag":"Books","deadline":null,"completed":true}
+        ]"#,
+    )
+    .unwrap();
+    fs::write(
+        config_home.join("silver-tasks").join("config.toml"),
+        "[formats]\ncompact = \"{id}|{name:<8}|{status}\"\n",
+    )
+    .unwrap();
+
+    let list = |args: &[&str]| {
+        let mut cmd = Command::new(cargo_bin_cmd());
+        cmd.env("HOME", temp_dir.path());
+        cmd.env("XDG_CONFIG_HOME", &config_home);
+        cmd.arg("list").args(args);
+        cmd
+    };
+
+    // Inline template: one line per task, tags sorted, no headers
+    list(&["--format", "{tag}: {name} {deadline:%Y/%m/%d}"])
+        .assert()
+        .success()
+        .stdout("Books: Read \nWriting: Essay 2099/02/20\n");
+
+    // Named format from the config
+    list(&["--format", "compact"])
+        .assert()
+        .success()
+        .stdout("002|Read    |done\n001|Essay   |open\n");
+
+    list(&["--format", "wide"])
+        .assert()
+        .code(3)
+        .stderr(predicate::str::contains("Unknown format \"wide\""));
+    list(&["--format", "{title}"])
+        .assert()
+        .code(3)
+        .stderr(predicate::str::contains("unknown field \"title\""));
+    list(&["--format", "{deadline:%H}"])
+        .assert()
+        .code(3)
+        .stderr(predicate::str::contains("Invalid date format \"%H\""));
+}
+
+#[test]
+fn test_list_table_shrinks_name_column() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(
+        temp_dir.path().join(".tasks.json"),
+        r#"[
+            {"id":1,"name":"Prepare the complete lab report for the automata course","tag":"Lab","deadline":"2099-02-20"},
+            {"id":2,"name":"Essay","tag":"Writing","deadline":"2020-01-01"}
+        ]"#,
+    )
+    .unwrap();
+
+    let mut cmd = Command::new(cargo_bin_cmd());
+    cmd.env("HOME", temp_dir.path());
+    cmd.env("COLUMNS", "70");
+    cmd.args(["list", "--table"]);
+
+    let out = cmd.assert().success().get_output().stdout.clone();
+    let text = String::from_utf8(out).unwrap();
+    let lines: Vec<&str> = text.lines().collect();
+
+    assert!(lines[1].contains("ID") && lines[1].contains("Days Left"));
+    // Sorted by deadline across tags: the overdue essay comes first
+    assert!(lines[3].contains("002") && lines[3].contains("overdue"));
+    assert!(lines[4].contains("001") && lines[4].contains('…'));
+    assert!(lines[4].contains("20.02.2099"));
+    assert!(lines.iter().all(|l| l.chars().count() <= 70));
+
+    // Any column can be the sort key
+    let mut cmd = Command::new(cargo_bin_cmd());
+    cmd.env("HOME", temp_dir.path());
+    cmd.args(["list", "--table", "--sort", "tag"]);
+
+    let out = cmd.assert().success().get_output().stdout.clone();
+    let text = String::from_utf8(out).unwrap();
+    assert!(text.find("Lab").unwrap() < text.find("Writing").unwrap());
+    assert!(text.contains("Prepare the complete lab report for the automata course"));
+}
+
+#[test]
+fn test_agenda_buckets_and_horizon() {
+    let temp_dir = TempDir::new().unwrap();
+    let today = chrono::Local::now().date_naive();
+    let day = |offset: i64| (today + chrono::Duration::days(offset)).format("%Y-%m-%d");
+    fs::write(
+        temp_dir.path().join(".tasks.json"),
+        format!(
+            r#"[
+                {{"id":1,"name":"Essay","tag":"Writing","deadline":"{}"}},
+                {{"id":2,"name":"Lab","tag":"Physics","deadline":"{}"}},
+                {{"id":3,"name":"Thesis","tag":"Research","deadline":"{}"}},
+                {{"id":4,"name":"Read","tag":"Books","deadline":null}},
+                {{"id":5,"name":"Done","tag":"Books","deadline":"{}","completed":true}}
+            ]"#,
+            day(-1),
+            day(0),
+            day(60),
+            day(0)
+        ),
+    )
+    .unwrap();
+
+    let mut cmd = Command::new(cargo_bin_cmd());
+    cmd.env("HOME", temp_dir.path());
+    cmd.arg("agenda");
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Overdue\n    001 - Essay [Writing]",
+        ))
+        .stdout(predicate::str::contains("Today\n    002 - Lab [Physics]"))
+        .stdout(predicate::str::contains(
+            "Later\n    003 - Thesis [Research]",
+        ))
+        .stdout(predicate::str::contains(
+            "No deadline\n    004 - Read [Books]",
+        ))
+        .stdout(predicate::str::contains("Done").not());
+
+    // The horizon drops tasks due further ahead
+    let mut cmd = Command::new(cargo_bin_cmd());
+    cmd.env("HOME", temp_dir.path());
+    cmd.args(["agenda", "--days", "7", "--output", "json"]);
+
+    let out = cmd.assert().success().get_output().stdout.clone();
+    let json: serde_json::Value = serde_json::from_slice(&out).unwrap();
+    let buckets: Vec<&str> = json["tasks"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["bucket"].as_str().unwrap())
+        .collect();
+    assert_eq!(buckets, vec!["overdue", "today", "no_deadline"]);
+}
+
+#[test]
+fn test_calendar_month_grid() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(
+        temp_dir.path().join(".tasks.json"),
+        r#"[
+            {"id":1,"name":"Essay","tag":"Writing","deadline":"2021-02-10"},
+            {"id":2,"name":"Lab","tag":"Physics","deadline":"2021-02-10"},
+            {"id":3,"name":"Done","tag":"Books","deadline":"2021-02-12","completed":true},
+            {"id":4,"name":"Thesis","tag":"Research","deadline":"2021-03-01"}
+        ]"#,
+    )
+    .unwrap();
+
+    let mut cmd = Command::new(cargo_bin_cmd());
+    cmd.env("HOME", temp_dir.path()).env("COLUMNS", "100");
+    cmd.args(["calendar", "--month", "2021-02"]);
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::starts_with("February 2021\n"))
+        .stdout(predicate::str::contains("10 (2)!"))
+        .stdout(predicate::str::contains("Essay"))
+        .stdout(predicate::str::contains("Done").not())
+        .stdout(predicate::str::contains("Thesis").not());
+
+    let mut cmd = Command::new(cargo_bin_cmd());
+    cmd.env("HOME", temp_dir.path());
+    cmd.args(["calendar", "--month", "2021-02", "--output", "json"]);
+
+    let out = cmd.assert().success().get_output().stdout.clone();
+    let json: serde_json::Value = serde_json::from_slice(&out).unwrap();
+    let days = json["days"].as_array().unwrap();
+    assert_eq!(days.len(), 1);
+    assert_eq!(days[0]["date"], "2021-02-10");
+    assert_eq!(days[0]["tasks"].as_array().unwrap().len(), 2);
+
+    let mut cmd = Command::new(cargo_bin_cmd());
+    cmd.env("HOME", temp_dir.path());
+    cmd.args(["calendar", "--month", "Feb"]);
+    cmd.assert()
+        .code(3)
+        .stderr(predicate::str::contains("Invalid month \"Feb\""));
+}
+
+#[test]
+fn test_workload_warning_and_load() {
+    let temp_dir = TempDir::new().unwrap();
+    let today = chrono::Local::now().date_naive();
+    let day = |offset: i64| (today + chrono::Duration::days(offset)).format("%Y-%m-%d");
+    fs::write(
+        temp_dir.path().join(".tasks.json"),
+        format!(
+            r#"[
+                {{"id":1,"name":"Essay","tag":"Writing","deadline":"{}"}},
+                {{"id":2,"name":"Lab","tag":"Physics","deadline":"{}"}},
+                {{"id":3,"name":"Quiz","tag":"Physics","deadline":"{}","completed":true}}
+            ]"#,
+            day(1),
+            day(2),
+            day(1)
+        ),
+    )
+    .unwrap();
+
+    let mut cmd = Command::new(cargo_bin_cmd());
+    cmd.env("HOME", temp_dir.path());
+    cmd.args(["add", "Talk", "Seminar", &day(1).to_string()]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Created task 004"))
+        .stderr(predicate::str::contains(format!(
+            "Warning: 2 other tasks are due within 2 days of {}",
+            (today + chrono::Duration::days(1)).format("%d.%m.%Y")
+        )));
+
+    // A higher threshold silences the warning
+    let mut cmd = Command::new(cargo_bin_cmd());
+    cmd.env("HOME", temp_dir.path())
+        .env("SILVER_TASKS_WORKLOAD_WARN_AT", "5");
+    cmd.args(["add", "Read", "Books", &day(1).to_string()]);
+    cmd.assert()
+        .success()
+        .stderr(predicate::str::contains("Warning").not());
+
+    let mut cmd = Command::new(cargo_bin_cmd());
+    cmd.env("HOME", temp_dir.path());
+    cmd.args(["load", "--span", "3"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Deadlines per day"))
+        .stdout(predicate::str::contains("███ 3 busy"))
+        .stdout(predicate::str::contains("█ 1\n"));
+
+    let mut cmd = Command::new(cargo_bin_cmd());
+    cmd.env("HOME", temp_dir.path());
+    cmd.args(["load", "--by", "week", "--span", "2", "--output", "json"]);
+    let out = cmd.assert().success().get_output().stdout.clone();
+    let json: serde_json::Value = serde_json::from_slice(&out).unwrap();
+    assert_eq!(json["by"], "week");
+    assert_eq!(json["periods"].as_array().unwrap().len(), 2);
+    let total: u64 = json["periods"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|p| p["tasks"].as_u64().unwrap())
+        .sum();
+    assert_eq!(total, 4);
+}
+
+#[test]
+fn test_stats_report() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(
+        temp_dir.path().join(".tasks.json"),
+        r#"[
+            {"id":1,"name":"Lab","tag":"Physics","deadline":"2026-10-10","completed":true,
+             "created_at":"2026-10-01T10:00:00+00:00","completed_at":"2026-10-09T10:00:00+00:00"},
+            {"id":2,"name":"Quiz","tag":"Physics","deadline":"2026-10-10","completed":true,
+             "created_at":"2026-10-01T10:00:00+00:00","completed_at":"2026-10-12T10:00:00+00:00"},
+            {"id":3,"name":"Read","tag":"Books","deadline":null}
+        ]"#,
+    )
+    .unwrap();
+    fs::write(
+        temp_dir.path().join(".tasks-archive.json"),
+        r#"[
+            {"id":1,"archived_on":"2026-10-20","task":{"id":9,"name":"Essay","tag":"Übungen",
+             "deadline":"2026-10-20","completed":true,"completed_at":"2026-10-15T10:00:00+00:00"}}
+        ]"#,
+    )
+    .unwrap();
+
+    let mut cmd = Command::new(cargo_bin_cmd());
+    cmd.env("HOME", temp_dir.path());
+    cmd.args(["stats", "--since", "2026-10-01", "--until", "2026-10-31"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Statistics 01.10.2026 - 31.10.2026",
+        ))
+        .stdout(predicate::str::contains("3 total"))
+        .stdout(predicate::str::contains("Average lead time   9.5 days"))
+        .stdout(predicate::str::contains(
+            "Physics  █████░░░░░  50% (1 of 2)",
+        ))
+        .stdout(predicate::str::contains(
+            "Übungen  ██████████ 100% (1 of 1)",
+        ));
+
+    let mut cmd = Command::new(cargo_bin_cmd());
+    cmd.env("HOME", temp_dir.path());
+    cmd.args([
+        "stats",
+        "--since",
+        "2026-10-11",
+        "--until",
+        "2026-10-31",
+        "--output",
+        "json",
+    ]);
+    let out = cmd.assert().success().get_output().stdout.clone();
+    let json: serde_json::Value = serde_json::from_slice(&out).unwrap();
+    assert_eq!(json["completed"], 2);
+    assert_eq!(json["weeks"].as_array().unwrap().len(), 4);
+    assert_eq!(json["tags"][0]["tag"], "Physics");
+    assert_eq!(json["tags"][0]["late"], 1);
+
+    let mut cmd = Command::new(cargo_bin_cmd());
+    cmd.env("HOME", temp_dir.path());
+    cmd.args(["stats", "--since", "2026-11-01", "--until", "2026-10-01"]);
+    cmd.assert().code(3);
+}
+
+#[test]
+fn test_completion_is_timestamped() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let mut cmd = Command::new(cargo_bin_cmd());
+    cmd.env("HOME", temp_dir.path());
+    cmd.args(["add", "Lab", "Physics"]);
+    cmd.assert().success();
+
+    let mut cmd = Command::new(cargo_bin_cmd());
+    cmd.env("HOME", temp_dir.path());
+    cmd.args(["remove", "1"]);
+    cmd.assert().success();
+
+    let content = fs::read_to_string(temp_dir.path().join(".tasks.json")).unwrap();
+    let tasks: serde_json::Value = serde_json::from_str(&content).unwrap();
+    assert!(tasks[0]["created_at"].is_string());
+    assert!(tasks[0]["completed_at"].is_string());
+}
+
+#[test]
+fn test_burndown_chart_and_svg() {
+    let temp_dir = TempDir::new().unwrap();
+    let today = chrono::Local::now().date_naive();
+    let at = |offset: i64| {
+        (today + chrono::Duration::days(offset))
+            .format("%Y-%m-%dT12:00:00+00:00")
+            .to_string()
+    };
+    fs::write(
+        temp_dir.path().join(".tasks.json"),
+        format!(
+            r#"[
+                {{"id":1,"name":"A","tag":"Automata","deadline":null,"created_at":"{c}"}},
+                {{"id":2,"name":"B","tag":"Automata","deadline":null,"created_at":"{c}",
+                  "completed":true,"completed_at":"{d}"}},
+                {{"id":3,"name":"C","tag":"Other","deadline":null,"created_at":"{c}"}}
+            ]"#,
+            c = at(-10),
+            d = at(-5)
+        ),
+    )
+    .unwrap();
+    let svg = temp_dir.path().join("burndown.svg");
+
+    let mut cmd = Command::new(cargo_bin_cmd());
+    cmd.env("HOME", temp_dir.path());
+    cmd.args([
+        "burndown",
+        "--tag",
+        "Automata",
+        "--to",
+        &(today + chrono::Duration::days(10))
+            .format("%Y-%m-%d")
+            .to_string(),
+        "--svg",
+        svg.to_str().unwrap(),
+    ]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Burndown Automata"))
+        .stdout(predicate::str::contains("2 ┤"))
+        .stdout(predicate::str::contains(format!(
+            "1 open on {}, ideal 1: on track",
+            today.format("%d.%m.%Y")
+        )))
+        .stdout(predicate::str::contains("Wrote"));
+    assert!(fs::read_to_string(&svg).unwrap().starts_with("<svg"));
+
+    let mut cmd = Command::new(cargo_bin_cmd());
+    cmd.env("HOME", temp_dir.path());
+    cmd.args(["burndown", "--tag", "Automata", "--output", "json"]);
+    let out = cmd.assert().success().get_output().stdout.clone();
+    let json: serde_json::Value = serde_json::from_slice(&out).unwrap();
+    let days = json["days"].as_array().unwrap();
+    assert_eq!(days.first().unwrap()["remaining"], 2);
+    assert_eq!(days.last().unwrap()["remaining"], 1);
+
+    let mut cmd = Command::new(cargo_bin_cmd());
+    cmd.env("HOME", temp_dir.path());
+    cmd.args(["burndown", "--tag", "Nope"]);
+    cmd.assert().code(3);
+}
+
+#[test]
+fn test_burndown_defaults_on_fresh_list() {
+    let temp_dir = TempDir::new().unwrap();
+    let burndown = |args: &[&str]| {
+        let mut cmd = Command::new(cargo_bin_cmd());
+        cmd.env("HOME", temp_dir.path());
+        cmd.arg("burndown").args(args);
+        cmd.assert()
+    };
+
+    let mut cmd = Command::new(cargo_bin_cmd());
+    cmd.env("HOME", temp_dir.path());
+    cmd.args(["add", "Essay", "Writing"]);
+    cmd.assert().success();
+
+    let today = chrono::Local::now().date_naive();
+    burndown(&[])
+        .success()
+        .stdout(predicate::str::contains(format!(
+            "{} - {}",
+            (today - chrono::Duration::days(28)).format("%d.%m.%Y"),
+            today.format("%d.%m.%Y")
+        )));
+    let tomorrow = (today + chrono::Duration::days(1))
+        .format("%Y-%m-%d")
+        .to_string();
+    burndown(&["--from", &tomorrow]).success();
+    burndown(&["--from", &tomorrow, "--to", &tomorrow])
+        .code(3)
+        .stderr(predicate::str::contains("--from must be before --to"));
+}
+
+#[test]
+fn test_csv_export_and_import() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(
+        temp_dir.path().join(".tasks.json"),
+        r#"[
+            {"id":1,"name":"Read \"Dune\", part 1","tag":"Books","deadline":"2026-03-01","uid":"a1@silver-tasks"},
+            {"id":2,"name":"Lab","tag":"Physics","deadline":null,"completed":true,"uid":"a2@silver-tasks"}
+        ]"#,
+    )
+    .unwrap();
+
+    let mut cmd = Command::new(cargo_bin_cmd());
+    cmd.env("HOME", temp_dir.path());
+    cmd.args(["export", "--format", "csv"]);
+    cmd.assert().success().stdout(
+        "id,name,tag,deadline,completed,created_at,completed_at,uid,priority,extras\n\
+         1,\"Read \"\"Dune\"\", part 1\",Books,2026-03-01,false,,,a1@silver-tasks,,\n\
+         2,Lab,Physics,,true,,,a2@silver-tasks,,\n",
+    );
+
+    let out = temp_dir.path().join("physics.csv");
+    let mut cmd = Command::new(cargo_bin_cmd());
+    cmd.env("HOME", temp_dir.path());
+    cmd.args(["export", "--format", "csv", "--filter", "tag=Physics", "-o"])
+        .arg(&out);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Exported 1 task to"));
+    assert_eq!(fs::read_to_string(&out).unwrap().lines().count(), 2);
+
+    let input = temp_dir.path().join("in.csv");
+    fs::write(
+        &input,
+        "Title,Course,Due\nEssay,Writing,15.02.2026\n,Physics,\nQuiz,Physics,someday\n",
+    )
+    .unwrap();
+    let mut cmd = Command::new(cargo_bin_cmd());
+    cmd.env("HOME", temp_dir.path());
+    cmd.args(["import", "--format", "csv"]).arg(&input).args([
+        "--map",
+        "Title=name",
+        "--map",
+        "Course=tag",
+        "--map",
+        "Due=deadline",
+    ]);
+    cmd.assert()
+        .code(5)
+        .stdout(predicate::str::contains("Created new tag: Writing"))
+        .stdout(predicate::str::contains("Imported 1 task from"))
+        .stderr(predicate::str::contains("Row 2: Task name cannot be empty"))
+        .stderr(predicate::str::contains("Row 3: Invalid date format"))
+        .stderr(predicate::str::contains(
+            "2 of 3 rows could not be imported",
+        ));
+
+    let mut cmd = Command::new(cargo_bin_cmd());
+    cmd.env("HOME", temp_dir.path());
+    cmd.arg("list");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("003 - Essay"));
+
+    // Unknown fields and flags of other formats are rejected before anything is read
+    for (format, flag, value, message) in [
+        ("csv", "--map", "Course=tagg", "Unknown field \"tagg\""),
+        (
+            "todotxt",
+            "--map",
+            "Title=name",
+            "--map only applies to --format csv",
+        ),
+        (
+            "csv",
+            "--tag-from",
+            "categories",
+            "--tag-from only applies to --format ics",
+        ),
+    ] {
+        let mut cmd = Command::new(cargo_bin_cmd());
+        cmd.env("HOME", temp_dir.path());
+        cmd.args(["import", "--format", format])
+            .arg(&input)
+            .args([flag, value]);
+        cmd.assert()
+            .code(3)
+            .stderr(predicate::str::contains(message));
+    }
+
+    // Nothing imported at all is a plain validation error
+    fs::write(
+        &input,
+        "name,tag,deadline
+Quiz,Physics,someday
+",
+    )
+    .unwrap();
+    let mut cmd = Command::new(cargo_bin_cmd());
+    cmd.env("HOME", temp_dir.path());
+    cmd.args(["import", "--format", "csv"]).arg(&input);
+    cmd.assert().code(3).stderr(predicate::str::contains(
+        "1 of 1 rows could not be imported",
+    ));
+}
+
+#[test]
+fn test_ics_export_keeps_uids() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(
+        temp_dir.path().join(".tasks.json"),
+        r#"[
+            {"id":1,"name":"Essay, draft","tag":"Writing","deadline":"2026-03-01"},
+            {"id":2,"name":"Lab","tag":"Physics","deadline":null,"completed":true},
+            {"id":3,"name":"Quiz","tag":"Physics","deadline":"2026-03-05","uid":"quiz@example.com"}
+        ]"#,
+    )
+    .unwrap();
+
+    let export = |args: &[&str]| {
+        let mut cmd = Command::new(cargo_bin_cmd());
+        cmd.env("HOME", temp_dir.path());
+        cmd.args(["export", "--format", "ics"]).args(args);
+        let output = cmd.assert().success().get_output().stdout.clone();
+        String::from_utf8(output).unwrap()
+    };
+    let uids = |ics: &str| -> Vec<String> {
+        ics.lines()
+            .filter_map(|l| l.strip_prefix("UID:"))
+            .map(str::to_string)
+            .collect()
+    };
+
+    // Neither a dry run nor a format that does not sync by UID changes the task file
+    let tasks_file = temp_dir.path().join(".tasks.json");
+    let before = fs::read_to_string(&tasks_file).unwrap();
+    // Every entry still gets a UID, but the new ones are not saved
+    let dry_run = uids(&export(&["--dry-run"]));
+    assert_eq!(dry_run.len(), 3);
+    assert_eq!(dry_run[2], "quiz@example.com");
+    assert_ne!(uids(&export(&["--dry-run"]))[0], dry_run[0]);
+    let mut cmd = Command::new(cargo_bin_cmd());
+    cmd.env("HOME", temp_dir.path());
+    cmd.args(["export", "--format", "csv"]);
+    cmd.assert().success();
+    assert_eq!(fs::read_to_string(&tasks_file).unwrap(), before);
+    assert!(!temp_dir.path().join(".tasks-backups").exists());
+
+    let first = export(&[]);
+    assert!(first.starts_with("BEGIN:VCALENDAR\r\n"));
+    assert!(first
+        .contains("SUMMARY:Essay\\, draft\r\nCATEGORIES:Writing\r\nDUE;VALUE=DATE:20260301\r\n"));
+    assert!(first.contains("STATUS:COMPLETED\r\n"));
+    assert_eq!(uids(&first).len(), 3);
+    assert_eq!(uids(&first)[2], "quiz@example.com");
+    assert_eq!(uids(&export(&[])), uids(&first));
+
+    let events = export(&["--events"]);
+    assert_eq!(events.matches("BEGIN:VEVENT").count(), 2);
+    assert!(events.contains("DTSTART;VALUE=DATE:20260305\r\nDTEND;VALUE=DATE:20260306\r\n"));
+
+    let mut cmd = Command::new(cargo_bin_cmd());
+    cmd.env("HOME", temp_dir.path());
+    cmd.args(["export", "--format", "csv", "--events"]);
+    cmd.assert().code(3).stderr(predicate::str::contains(
+        "--events only applies to --format ics",
+    ));
+}
+
+#[test]
+fn test_ics_import_deduplicates_on_uid() {
+    let temp_dir = TempDir::new().unwrap();
+    let feed = temp_dir.path().join("course.ics");
+    let write_feed = |due: &str| {
+        fs::write(
+            &feed,
+            format!(
+                "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nX-WR-CALNAME:Physics\r\n\
+                 BEGIN:VEVENT\r\nUID:hw-1@lms\r\nSUMMARY:Homework 1\r\nDTSTART;VALUE=DATE:{}\r\nEND:VEVENT\r\n\
+                 BEGIN:VTODO\r\nUID:hw-2@lms\r\nSUMMARY:Homework 2\r\nCATEGORIES:Labs\r\nDUE:20260320T120000\r\nEND:VTODO\r\n\
+                 END:VCALENDAR\r\n",
+                due
+            ),
+        )
+        .unwrap();
+    };
+    let import = |args: &[&str]| {
+        let mut cmd = Command::new(cargo_bin_cmd());
+        cmd.env("HOME", temp_dir.path());
+        cmd.args(["import", "--format", "ics"])
+            .arg(&feed)
+            .args(args);
+        cmd.assert()
+    };
+
+    write_feed("20260310");
+    import(&["--tag-from", "calendar-name"])
+        .success()
+        .stdout(predicate::str::contains("Created new tag: Physics"))
+        .stdout(predicate::str::contains("Imported 2 tasks from"));
+
+    write_feed("20260312");
+    import(&["--tag-from", "CATEGORIES"])
+        .success()
+        .stdout(predicate::str::contains("Imported 0 tasks from"))
+        .stdout(predicate::str::contains(
+            "Updated deadline of task 001: 10.03.2026 -> 12.03.2026",
+        ))
+        .stdout(predicate::str::contains("Skipped 1 unchanged task"));
+
+    let tasks: Vec<serde_json::Value> =
+        serde_json::from_str(&fs::read_to_string(temp_dir.path().join(".tasks.json")).unwrap())
+            .unwrap();
+    assert_eq!(tasks.len(), 2);
+    assert_eq!(tasks[0]["deadline"], "2026-03-12");
+    assert_eq!(tasks[1]["tag"], "Physics");
+}
+
+#[test]
+fn test_todotxt_round_trip() {
+    let source = TempDir::new().unwrap();
+    fs::write(
+        source.path().join(".tasks.json"),
+        r#"[
+            {"id":1,"name":"Call lab","tag":"Physics 101","deadline":"2026-03-01","completed":false,"priority":"A","uid":"c1@silver-tasks"},
+            {"id":2,"name":"Essay @home","tag":"Writing","deadline":null,"completed":true,"priority":"C","uid":"e1@silver-tasks"}
+        ]"#,
+    )
+    .unwrap();
+    let file = source.path().join("todo.txt");
+    let mut cmd = Command::new(cargo_bin_cmd());
+    cmd.env("HOME", source.path());
+    cmd.args(["export", "--format", "todotxt", "-o"]).arg(&file);
+    cmd.assert().success();
+    assert_eq!(
+        fs::read_to_string(&file).unwrap(),
+        "(A) Call lab +Physics%20101 due:2026-03-01 uid:c1@silver-tasks\n\
+         x Essay @home +Writing pri:C uid:e1@silver-tasks\n"
+    );
+
+    let target = TempDir::new().unwrap();
+    let mut cmd = Command::new(cargo_bin_cmd());
+    cmd.env("HOME", target.path());
+    cmd.args(["import", "--format", "todotxt"]).arg(&file);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Imported 2 tasks from"));
+
+    let read = |dir: &TempDir| -> Vec<serde_json::Value> {
+        let content = fs::read_to_string(dir.path().join(".tasks.json")).unwrap();
+        let mut tasks: Vec<serde_json::Value> = serde_json::from_str(&content).unwrap();
+        for task in &mut tasks {
+            task.as_object_mut().unwrap().remove("created_at");
+        }
+        tasks
+    };
+    assert_eq!(read(&target), read(&source));
+}
+
+#[test]
+fn test_taskwarrior_import_and_export() {
+    let temp_dir = TempDir::new().unwrap();
+    let file = temp_dir.path().join("tw.json");
+    fs::write(
+        &file,
+        r#"{"id":1,"description":"Lab report","due":"20260301T230000Z","project":"Physics","status":"pending","uuid":"6a1b2c3d-4e5f-4a7b-8c9d-0e1f2a3b4c5d","urgency":8.2,"scheduled":"20260225T080000Z"}
+{"id":2,"description":"Read paper","status":"waiting","tags":["reading"],"uuid":"7b2c3d4e-5f6a-4b8c-9d0e-1f2a3b4c5d6e"}
+"#,
+    )
+    .unwrap();
+
+    let mut cmd = Command::new(cargo_bin_cmd());
+    cmd.env("HOME", temp_dir.path());
+    cmd.args(["import", "--format", "taskwarrior"]).arg(&file);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Created new tag: reading"))
+        .stdout(predicate::str::contains("Imported 2 tasks from"));
+
+    let tasks: Vec<serde_json::Value> =
+        serde_json::from_str(&fs::read_to_string(temp_dir.path().join(".tasks.json")).unwrap())
+            .unwrap();
+    assert_eq!(tasks[0]["tag"], "Physics");
+    assert_eq!(tasks[0]["extras"]["scheduled"], "20260225T080000Z");
+    assert_eq!(tasks[1]["extras"]["status"], "waiting");
+
+    let mut cmd = Command::new(cargo_bin_cmd());
+    cmd.env("HOME", temp_dir.path());
+    cmd.args(["add", "Quiz", "Math"]);
+    cmd.assert().success();
+
+    let mut cmd = Command::new(cargo_bin_cmd());
+    cmd.env("HOME", temp_dir.path());
+    cmd.args(["export", "--format", "taskwarrior"]);
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let exported: Vec<serde_json::Value> = serde_json::from_slice(&output).unwrap();
+    assert_eq!(exported[0]["scheduled"], "20260225T080000Z");
+    assert_eq!(exported[0]["uuid"], "6a1b2c3d-4e5f-4a7b-8c9d-0e1f2a3b4c5d");
+    assert_eq!(exported[1]["status"], "waiting");
+    assert!(exported[1].get("project").is_none());
+    assert_eq!(exported[2]["project"], "Math");
+    // New tasks get a UUID on first export
+    let uuid = exported[2]["uuid"].as_str().unwrap();
+    assert_eq!(uuid.len(), 36);
+    assert_eq!(&uuid[14..15], "4");
+}
+
+#[test]
+fn test_markdown_export_and_import() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(
+        temp_dir.path().join(".tasks.json"),
+        r#"[
+            {"id":1,"name":"Lab","tag":"Physics","deadline":"2026-03-05"},
+            {"id":2,"name":"Essay","tag":"Writing","deadline":null,"completed":true},
+            {"id":3,"name":"Quiz","tag":"Physics","deadline":"2026-03-01"}
+        ]"#,
+    )
+    .unwrap();
+
+    let mut cmd = Command::new(cargo_bin_cmd());
+    cmd.env("HOME", temp_dir.path());
+    cmd.args(["export", "--format", "markdown"]);
+    cmd.assert().success().stdout(
+        "## Physics\n\n\
+         - [ ] Quiz (due 01.03.2026)\n\
+         - [ ] Lab (due 05.03.2026)\n\
+         \n\
+         ## Writing\n\n\
+         - [x] Essay\n",
+    );
+
+    let notes = temp_dir.path().join("notes.md");
+    fs::write(
+        &notes,
+        "# Study group\n\nWe split the reading.\n\n## Chemistry\n\n- [ ] Titration lab (due 10.03.2026)\n- [x] Read chapter 1\n",
+    )
+    .unwrap();
+    let mut cmd = Command::new(cargo_bin_cmd());
+    cmd.env("HOME", temp_dir.path());
+    cmd.args(["import", "--format", "markdown"]).arg(&notes);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Created new tag: Chemistry"))
+        .stdout(predicate::str::contains("Imported 2 tasks from"));
+
+    let mut cmd = Command::new(cargo_bin_cmd());
+    cmd.env("HOME", temp_dir.path());
+    cmd.arg("list");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("004 - Titration lab"));
+}
+
+#[test]
+fn test_org_reimport_updates_tasks() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(
+        temp_dir.path().join(".tasks.json"),
+        r#"[
+            {"id":1,"name":"Lab report","tag":"Physics 101","deadline":"2026-02-15","uid":"6a1b2c3d-4e5f-4a7b-8c9d-0e1f2a3b4c5d"},
+            {"id":2,"name":"Quiz","tag":"Math","deadline":null,"uid":"7b2c3d4e-5f6a-4b8c-9d0e-1f2a3b4c5d6e"}
+        ]"#,
+    )
+    .unwrap();
+    let file = temp_dir.path().join("tasks.org");
+    let mut cmd = Command::new(cargo_bin_cmd());
+    cmd.env("HOME", temp_dir.path());
+    cmd.args(["export", "--format", "org", "-o"]).arg(&file);
+    cmd.assert().success();
+    let org = fs::read_to_string(&file).unwrap();
+    assert!(org.contains(
+        "* Physics 101\n** TODO Lab report :Physics_101:\n   DEADLINE: <2026-02-15 Sun>\n"
+    ));
+
+    // Edited in Emacs: the lab is done and moved, the quiz renamed, a new task added
+    let edited = org
+        .replace("** TODO Lab report", "** DONE Lab report")
+        .replace("<2026-02-15 Sun>", "<2026-02-17 Tue>")
+        .replace("TODO Quiz", "TODO Quiz 1")
+        + "** TODO Problem set\n";
+    fs::write(&file, edited).unwrap();
+
+    let mut cmd = Command::new(cargo_bin_cmd());
+    cmd.env("HOME", temp_dir.path());
+    cmd.args(["import", "--format", "org"]).arg(&file);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Imported 1 task from"))
+        .stdout(predicate::str::contains(
+            "Updated deadline of task 001: 15.02.2026 -> 17.02.2026",
+        ))
+        .stdout(predicate::str::contains("Completed task 001"))
+        .stdout(predicate::str::contains(
+            "Updated name of task 002: \"Quiz\" -> \"Quiz 1\"",
+        ));
+
+    let tasks: Vec<serde_json::Value> =
+        serde_json::from_str(&fs::read_to_string(temp_dir.path().join(".tasks.json")).unwrap())
+            .unwrap();
+    assert_eq!(tasks.len(), 3);
+    assert_eq!(tasks[0]["completed"], true);
+    assert_eq!(tasks[2]["name"], "Problem set");
+    assert_eq!(tasks[2]["tag"], "Physics 101");
+}
+
+#[test]
+fn test_report_html() {
+    let temp_dir = TempDir::new().unwrap();
+    let yesterday = chrono::Local::now().date_naive() - chrono::Duration::days(1);
+    fs::write(
+        temp_dir.path().join(".tasks.json"),
+        format!(
+            r#"[
+                {{"id":1,"name":"Lab <1>","tag":"Physics","deadline":"{}","completed":false}},
+                {{"id":2,"name":"Read","tag":"Books","deadline":null,"completed":false}}
+            ]"#,
+            yesterday.format("%Y-%m-%d")
+        ),
+    )
+    .unwrap();
+    let file = temp_dir.path().join("tasks.html");
+
+    let mut cmd = Command::new(cargo_bin_cmd());
+    cmd.env("HOME", temp_dir.path());
+    cmd.args(["--dry-run", "report", "html", "-o"]).arg(&file);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Would write report to"));
+    assert!(!file.exists());
+
+    let mut cmd = Command::new(cargo_bin_cmd());
+    cmd.env("HOME", temp_dir.path());
+    cmd.args(["report", "html", "-o"]).arg(&file);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Wrote report to"));
+    let html = fs::read_to_string(&file).unwrap();
+    assert!(html.contains("<style>"));
+    assert!(html.contains("<h3>Physics</h3>"));
+    assert!(html.contains("<tr class=\"overdue\"><td class=\"id\">001</td><td>Lab &lt;1&gt;</td>"));
+
+    let mut cmd = Command::new(cargo_bin_cmd());
+    cmd.env("HOME", temp_dir.path());
+    cmd.args(["report", "html"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::starts_with("<!DOCTYPE html>"));
+}
+
+#[test]
+fn test_backup_list_and_restore() {
+    let temp_dir = TempDir::new().unwrap();
+    let run = |args: &[&str]| {
+        let mut cmd = Command::new(cargo_bin_cmd());
+        cmd.env("HOME", temp_dir.path());
+        cmd.args(args);
+        cmd.assert().success()
+    };
+
+    run(&["backup", "list"]).stdout(predicate::str::contains("No backups yet"));
+    run(&["add", "Lab", "Physics"]);
+    run(&["add", "Quiz", "Physics"]);
+    run(&["remove", "1"]);
+
+    let output = run(&["--output", "json", "backup", "list"]);
+    let json: serde_json::Value = serde_json::from_slice(&output.get_output().stdout).unwrap();
+    let backups = json["backups"].as_array().unwrap();
+    let counts: Vec<u64> = backups
+        .iter()
+        .map(|b| b["tasks"].as_u64().unwrap())
+        .collect();
+    assert_eq!(counts, [0, 1, 2]);
+    let stamp = backups[2]["stamp"].as_str().unwrap().to_string();
+
+    run(&["--dry-run", "backup", "restore", &stamp])
+        .stdout(predicate::str::contains(
+            "~ 001 - Lab (Physics): done -> open",
+        ))
+        .stdout(predicate::str::contains("Would restore backup"));
+    let tasks = fs::read_to_string(temp_dir.path().join(".tasks.json")).unwrap();
+    assert!(tasks.contains("\"completed\": true"));
+
+    run(&["--yes", "backup", "restore", &stamp])
+        .stdout(predicate::str::contains("Restored backup"));
+    let tasks = fs::read_to_string(temp_dir.path().join(".tasks.json")).unwrap();
+    assert!(!tasks.contains("\"completed\": true"));
+
+    // The restore backed up the state it replaced
+    run(&["backup", "list"]).stdout(predicate::function(|out: &str| out.lines().count() == 4));
+
+    let mut cmd = Command::new(cargo_bin_cmd());
+    cmd.env("HOME", temp_dir.path());
+    cmd.args(["backup", "restore", "1999"]);
+    cmd.assert().failure().code(4);
+}